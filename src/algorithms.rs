@@ -0,0 +1,91 @@
+use crate::Graph;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub mod isomorphism;
+pub mod subgraph;
+pub mod components;
+pub mod scc;
+pub mod toposort;
+pub mod traversal;
+pub mod biconnected;
+pub mod bipartite;
+pub mod dominators;
+pub mod euler;
+
+pub use crate::algorithms::isomorphism::{is_isomorphic, find_isomorphism, find_isomorphism_by};
+pub use crate::algorithms::subgraph::{SubgraphMatcher, SubgraphMatches};
+pub use crate::algorithms::components::{weakly_connected_components, component_subgraphs};
+pub use crate::algorithms::scc::{strongly_connected_components, condensation};
+pub use crate::algorithms::toposort::{topological_sort, lexicographic_topological_sort, find_cycle, CycleError};
+pub use crate::algorithms::traversal::{Bfs, Dfs, DfsEvent, depth_first_search};
+pub use crate::algorithms::biconnected::{articulation_points, bridges, biconnected_components, two_edge_connected_components};
+pub use crate::algorithms::bipartite::{bipartition, Bipartition, OddCycleError};
+pub use crate::algorithms::dominators::{dominators, Dominators};
+pub use crate::algorithms::euler::{eulerian_trail, eulerian_circuit, undirected_eulerian_trail, undirected_eulerian_circuit, EulerStep, EulerError};
+
+///
+/// A snapshot of a graph's structure using dense `usize` indices in place of its IDs, which the
+/// algorithms here work on so that they only need to hash each ID once
+pub(crate) struct IndexedGraph<'a, G: Graph> {
+    pub(crate) ids: Vec<&'a G::ID>,
+    pub(crate) index: HashMap<&'a G::ID, usize>,
+    pub(crate) successors: Vec<Vec<usize>>,
+    pub(crate) predecessors: Vec<Vec<usize>>,
+}
+
+impl<'a, G> IndexedGraph<'a, G>
+    where G: Graph,
+          G::ID: Hash {
+
+    pub(crate) fn new(graph: &'a G) -> Self {
+        Self::with_order(graph, graph.nodes().map(|node| node.get_id()).collect())
+    }
+
+    ///
+    /// Indexes the nodes in order of their IDs, so that comparing indices compares IDs
+    pub(crate) fn sorted(graph: &'a G) -> Self
+        where G::ID: Ord {
+        let mut ids: Vec<&G::ID> = graph.nodes().map(|node| node.get_id()).collect();
+        ids.sort();
+        let mut output = Self::with_order(graph, ids);
+        for neighbors in output.successors.iter_mut().chain(output.predecessors.iter_mut()) {
+            neighbors.sort_unstable();
+        }
+        output
+    }
+
+    fn with_order(graph: &'a G, ids: Vec<&'a G::ID>) -> Self {
+        let index: HashMap<&G::ID, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        let successors = ids.iter()
+            .map(|id| graph.get_adjacent(id).map(|v| index[v]).collect())
+            .collect();
+        let predecessors = ids.iter()
+            .map(|id| graph.get_incoming(id).map(|u| index[u]).collect())
+            .collect();
+
+        IndexedGraph {
+            ids,
+            index,
+            successors,
+            predecessors,
+        }
+    }
+}
+
+impl<'a, G: Graph> IndexedGraph<'a, G> {
+
+    pub(crate) fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub(crate) fn id(&self, node: usize) -> &'a G::ID {
+        self.ids[node]
+    }
+
+    ///
+    /// The nodes with an edge to or from `node`
+    pub(crate) fn neighbors(&self, node: usize) -> impl Iterator<Item=usize> + '_ {
+        self.successors[node].iter().chain(&self.predecessors[node]).copied()
+    }
+}
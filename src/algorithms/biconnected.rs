@@ -0,0 +1,282 @@
+use crate::Graph;
+use crate::algorithms::IndexedGraph;
+use std::hash::Hash;
+
+///
+/// The graph's edges as undirected connections, each listing its endpoints and appearing in the
+/// adjacency list of both ends along with its position in the edge list
+struct Connections {
+    ends: Vec<(usize, usize)>,
+    adjacent: Vec<Vec<(usize, usize)>>,
+}
+
+impl Connections {
+
+    fn new<G: Graph>(graph: &G, nodes: &IndexedGraph<'_, G>) -> Self
+        where G::ID: Hash {
+        let mut ends = vec![];
+        let mut adjacent = vec![vec![]; nodes.len()];
+        for edge in graph.edges() {
+            let (u, v) = (nodes.index[edge.get_source()], nodes.index[edge.get_target()]);
+            adjacent[u].push((v, ends.len()));
+            if u != v {
+                adjacent[v].push((u, ends.len()));
+            }
+            ends.push((u, v));
+        }
+        Connections { ends, adjacent }
+    }
+}
+
+///
+/// What a depth first search over [`Connections`] finds out about how well connected it is
+struct Blocks {
+    articulation: Vec<bool>,
+    bridges: Vec<usize>,
+    components: Vec<Vec<usize>>,
+}
+
+///
+/// Hopcroft and Tarjan's algorithm, using an explicit stack so that long paths don't overflow
+fn blocks(connections: &Connections) -> Blocks {
+    const UNVISITED: usize = usize::MAX;
+    let count = connections.adjacent.len();
+    let mut order = vec![UNVISITED; count];
+    let mut low_link = vec![0; count];
+    let mut articulation = vec![false; count];
+    let mut bridges = vec![];
+    let mut components = vec![];
+    let mut component_of = vec![UNVISITED; count];
+    let mut edge_stack = vec![];
+    let mut next_order = 0;
+
+    for root in 0..count {
+        if order[root] != UNVISITED {
+            continue;
+        }
+        order[root] = next_order;
+        low_link[root] = next_order;
+        next_order += 1;
+        if connections.adjacent[root].iter().all(|&(next, _)| next == root) {
+            components.push(vec![root]);
+            continue;
+        }
+
+        // Each frame is a node, the edge the search came in on, and how many connections have been looked at
+        let mut frames = vec![(root, UNVISITED, 0)];
+        let mut root_children = 0;
+        while let Some(frame) = frames.last_mut() {
+            let (node, parent_edge, visited) = *frame;
+            if let Some(&(next, edge)) = connections.adjacent[node].get(visited) {
+                frame.2 += 1;
+                if edge == parent_edge || next == node {
+                    continue;
+                }
+                if order[next] == UNVISITED {
+                    order[next] = next_order;
+                    low_link[next] = next_order;
+                    next_order += 1;
+                    edge_stack.push(edge);
+                    if node == root {
+                        root_children += 1;
+                    }
+                    frames.push((next, edge, 0));
+                } else if order[next] < order[node] {
+                    low_link[node] = low_link[node].min(order[next]);
+                    edge_stack.push(edge);
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some(&(parent, _, _)) = frames.last() {
+                low_link[parent] = low_link[parent].min(low_link[node]);
+                if low_link[node] >= order[parent] {
+                    // Nothing below `node` reaches above `parent`, so the edges found since
+                    // `parent -> node` make up a block that only `parent` joins to the rest
+                    articulation[parent] |= parent != root;
+                    if low_link[node] > order[parent] {
+                        bridges.push(parent_edge);
+                    }
+                    let label = components.len();
+                    let mut members = vec![];
+                    loop {
+                        let edge = edge_stack.pop().expect("The tree edge is on the stack");
+                        let (u, v) = connections.ends[edge];
+                        for end in [u, v] {
+                            if component_of[end] != label {
+                                component_of[end] = label;
+                                members.push(end);
+                            }
+                        }
+                        if edge == parent_edge {
+                            break;
+                        }
+                    }
+                    components.push(members);
+                }
+            }
+        }
+        articulation[root] = root_children > 1;
+    }
+
+    Blocks {
+        articulation,
+        bridges,
+        components,
+    }
+}
+
+///
+/// Finds the nodes whose removal would disconnect the rest of their component, treating each edge
+/// of the graph as an undirected connection
+pub fn articulation_points<G>(graph: &G) -> Vec<&G::ID>
+    where G: Graph,
+          G::ID: Hash {
+    let nodes = IndexedGraph::new(graph);
+    let found = blocks(&Connections::new(graph, &nodes));
+    (0..nodes.len())
+        .filter(|&node| found.articulation[node])
+        .map(|node| nodes.id(node))
+        .collect()
+}
+
+///
+/// Finds the edges whose removal would disconnect their component, as the `(source, target)` pairs
+/// of [`Graph::edges`]. Parallel edges are never bridges, and on a directed graph `u -> v` and
+/// `v -> u` count as parallel
+pub fn bridges<G>(graph: &G) -> Vec<(&G::ID, &G::ID)>
+    where G: Graph,
+          G::ID: Hash {
+    let nodes = IndexedGraph::new(graph);
+    let connections = Connections::new(graph, &nodes);
+    blocks(&connections).bridges
+        .into_iter()
+        .map(|edge| {
+            let (u, v) = connections.ends[edge];
+            (nodes.id(u), nodes.id(v))
+        })
+        .collect()
+}
+
+///
+/// Groups the nodes into biconnected components, the largest sets that stay connected after
+/// removing any one node. Articulation points belong to every component they join, and a node
+/// without edges is a component on its own
+pub fn biconnected_components<G>(graph: &G) -> Vec<Vec<&G::ID>>
+    where G: Graph,
+          G::ID: Hash {
+    let nodes = IndexedGraph::new(graph);
+    blocks(&Connections::new(graph, &nodes)).components
+        .into_iter()
+        .map(|component| component.into_iter().map(|node| nodes.id(node)).collect())
+        .collect()
+}
+
+///
+/// Groups the nodes into 2-edge-connected components, the largest sets that stay connected after
+/// removing any one edge. These are what is left connected once every bridge is removed
+pub fn two_edge_connected_components<G>(graph: &G) -> Vec<Vec<&G::ID>>
+    where G: Graph,
+          G::ID: Hash {
+    let nodes = IndexedGraph::new(graph);
+    let connections = Connections::new(graph, &nodes);
+    let mut is_bridge = vec![false; connections.ends.len()];
+    for edge in blocks(&connections).bridges {
+        is_bridge[edge] = true;
+    }
+
+    let mut labels = vec![usize::MAX; nodes.len()];
+    let mut components = vec![];
+    let mut stack = vec![];
+    for root in 0..nodes.len() {
+        if labels[root] != usize::MAX {
+            continue;
+        }
+        labels[root] = components.len();
+        let mut component = vec![];
+        stack.push(root);
+        while let Some(node) = stack.pop() {
+            component.push(nodes.id(node));
+            for &(next, edge) in &connections.adjacent[node] {
+                if !is_bridge[edge] && labels[next] == usize::MAX {
+                    labels[next] = components.len();
+                    stack.push(next);
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::algorithms::{articulation_points, bridges, biconnected_components};
+    use crate::directed::{Undirected, new_hashed_undirected};
+    use crate::HashGraph;
+
+    fn sorted(components: Vec<Vec<&u32>>) -> Vec<Vec<u32>> {
+        let mut components: Vec<Vec<u32>> = components.into_iter()
+            .map(|component| {
+                let mut component: Vec<u32> = component.into_iter().copied().collect();
+                component.sort();
+                component
+            })
+            .collect();
+        components.sort();
+        components
+    }
+
+    ///
+    /// Two triangles joined through the link `2 - 3`, a spur `5 - 6` and a lone node 7
+    fn network() -> Undirected<u32, (), (), HashGraph<u32>> {
+        let mut g = new_hashed_undirected();
+        g.add_nodes(0..8).unwrap();
+        for (u, v) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (5, 6)] {
+            g.add_edge(&u, &v).unwrap();
+        }
+        g
+    }
+
+    #[test]
+    fn points_of_failure() {
+        let g = network();
+        let mut points: Vec<u32> = g.articulation_points().into_iter().copied().collect();
+        points.sort();
+        assert_eq!(points, vec![2, 3, 5]);
+
+        let mut links: Vec<(u32, u32)> = bridges(&g).into_iter()
+            .map(|(u, v)| (*u.min(v), *u.max(v)))
+            .collect();
+        links.sort();
+        assert_eq!(links, vec![(2, 3), (5, 6)]);
+    }
+
+    #[test]
+    fn components() {
+        let g = network();
+        assert_eq!(sorted(biconnected_components(&g)), vec![
+            vec![0, 1, 2], vec![2, 3], vec![3, 4, 5], vec![5, 6], vec![7],
+        ]);
+        assert_eq!(sorted(g.two_edge_connected_components()), vec![
+            vec![0, 1, 2], vec![3, 4, 5], vec![6], vec![7],
+        ]);
+    }
+
+    #[test]
+    fn long_paths_and_cycles() {
+        let mut path = new_hashed_undirected::<u32, (), ()>();
+        path.add_nodes(0..5_000).unwrap();
+        for i in 0..4_999 {
+            path.add_edge(&i, &(i + 1)).unwrap();
+        }
+        assert_eq!(path.bridges().len(), 4_999);
+        assert_eq!(articulation_points(&path).len(), 4_998);
+
+        path.add_edge(&4_999, &0).unwrap();
+        assert!(path.bridges().is_empty());
+        assert_eq!(path.biconnected_components().len(), 1);
+    }
+}
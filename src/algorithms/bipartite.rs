@@ -0,0 +1,139 @@
+use crate::Graph;
+use crate::algorithms::IndexedGraph;
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display, Formatter, Result};
+use std::hash::Hash;
+
+///
+/// The reason a graph can't be split into two sides: a cycle with an odd number of nodes, listed so
+/// that each ID is connected to the next and the last is connected back to the first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OddCycleError<ID> {
+    cycle: Vec<ID>,
+}
+
+impl<ID> OddCycleError<ID> {
+
+    pub fn cycle(&self) -> &[ID] {
+        &self.cycle
+    }
+
+    pub fn into_cycle(self) -> Vec<ID> {
+        self.cycle
+    }
+}
+
+impl<ID: Debug> Display for OddCycleError<ID> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "The graph has an odd cycle: ")?;
+        for id in &self.cycle {
+            write!(f, "{:?} - ", id)?;
+        }
+        write!(f, "{:?}", self.cycle[0])
+    }
+}
+
+impl<ID: Debug> std::error::Error for OddCycleError<ID> {}
+
+///
+/// The two sides of a bipartite graph
+pub type Bipartition<'a, ID> = (Vec<&'a ID>, Vec<&'a ID>);
+
+///
+/// Splits the nodes into two sides so that every edge goes between the sides, ignoring edge
+/// directions. Each component's first node in the graph goes on the first side
+pub fn bipartition<G>(graph: &G) -> std::result::Result<Bipartition<'_, G::ID>, OddCycleError<&G::ID>>
+    where G: Graph,
+          G::ID: Hash {
+    const UNVISITED: usize = usize::MAX;
+    let nodes = IndexedGraph::new(graph);
+    let mut side = vec![false; nodes.len()];
+    let mut parent = vec![UNVISITED; nodes.len()];
+    let mut queue = VecDeque::new();
+
+    for root in 0..nodes.len() {
+        if parent[root] != UNVISITED {
+            continue;
+        }
+        parent[root] = root;
+        queue.push_back(root);
+        while let Some(node) = queue.pop_front() {
+            for next in nodes.neighbors(node) {
+                if parent[next] == UNVISITED {
+                    parent[next] = node;
+                    side[next] = !side[node];
+                    queue.push_back(next);
+                } else if side[next] == side[node] {
+                    return Err(OddCycleError { cycle: odd_cycle(&nodes, &parent, node, next) });
+                }
+            }
+        }
+    }
+
+    let (first, second): (Vec<usize>, Vec<usize>) = (0..nodes.len()).partition(|&node| !side[node]);
+    Ok((
+        first.into_iter().map(|node| nodes.id(node)).collect(),
+        second.into_iter().map(|node| nodes.id(node)).collect(),
+    ))
+}
+
+///
+/// Builds the cycle closed by an edge between two nodes on the same side. A breadth first search
+/// puts them at the same depth, so climbing from both at once meets at their closest common ancestor
+fn odd_cycle<'a, G: Graph>(nodes: &IndexedGraph<'a, G>, parent: &[usize], u: usize, v: usize) -> Vec<&'a G::ID> {
+    let (mut up, mut down) = (vec![u], vec![]);
+    let (mut a, mut b) = (u, v);
+    while a != b {
+        down.push(b);
+        a = parent[a];
+        b = parent[b];
+        up.push(a);
+    }
+    up.extend(down.into_iter().rev());
+    up.into_iter().map(|node| nodes.id(node)).collect()
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::algorithms::bipartition;
+    use crate::directed::new_hashed_undirected;
+    use crate::{HashGraph, Graph};
+
+    #[test]
+    fn even_graphs_split() {
+        let mut g = new_hashed_undirected::<u32, (), ()>();
+        g.add_nodes(0..7).unwrap();
+        for (u, v) in [(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)] {
+            g.add_edge(&u, &v).unwrap();
+        }
+
+        let (left, right) = g.bipartition().unwrap();
+        assert_eq!(left.len() + right.len(), 7);
+        for edge in g.edges() {
+            assert_ne!(left.contains(&edge.get_source()), left.contains(&edge.get_target()));
+        }
+    }
+
+    #[test]
+    fn odd_cycles_are_reported() {
+        let mut g = new_hashed_undirected::<u32, (), ()>();
+        g.add_nodes(0..6).unwrap();
+        for (u, v) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (4, 5)] {
+            g.add_edge(&u, &v).unwrap();
+        }
+
+        let error = bipartition(&g).unwrap_err();
+        let cycle = error.cycle();
+        assert_eq!(cycle.len(), 5);
+        for (i, u) in cycle.iter().enumerate() {
+            assert!(g.contains_edge(u, cycle[(i + 1) % cycle.len()]));
+        }
+        assert!(error.to_string().starts_with("The graph has an odd cycle: "));
+
+        let mut looped: HashGraph<u32> = HashGraph::new();
+        looped.add_node(0).unwrap();
+        looped.add_edge(&0, &0).unwrap();
+        assert_eq!(bipartition(&looped).unwrap_err().into_cycle(), vec![&0]);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{Graph, HashGraph};
+use crate::algorithms::IndexedGraph;
+use std::hash::Hash;
+
+///
+/// Labels each node with the index of its component, treating every edge as undirected.
+/// Components are numbered in the order their first node appears in the graph
+pub(crate) fn component_labels<G: Graph>(nodes: &IndexedGraph<'_, G>) -> (Vec<usize>, usize) {
+    let mut labels = vec![usize::MAX; nodes.len()];
+    let mut count = 0;
+    let mut stack = vec![];
+
+    for root in 0..nodes.len() {
+        if labels[root] != usize::MAX {
+            continue;
+        }
+        labels[root] = count;
+        stack.push(root);
+        while let Some(node) = stack.pop() {
+            for neighbor in nodes.neighbors(node) {
+                if labels[neighbor] == usize::MAX {
+                    labels[neighbor] = count;
+                    stack.push(neighbor);
+                }
+            }
+        }
+        count += 1;
+    }
+
+    (labels, count)
+}
+
+///
+/// Groups the nodes into sets that are connected when edge directions are ignored. For an
+/// `Undirected` graph these are its connected components
+pub fn weakly_connected_components<G>(graph: &G) -> Vec<Vec<&G::ID>>
+    where G: Graph,
+          G::ID: Hash {
+    let nodes = IndexedGraph::new(graph);
+    let (labels, count) = component_labels(&nodes);
+    let mut components = vec![vec![]; count];
+    for (node, label) in labels.into_iter().enumerate() {
+        components[label].push(nodes.id(node));
+    }
+    components
+}
+
+///
+/// Splits the graph into one graph per weakly connected component, borrowing the IDs, weights and
+/// values of the original. Only the oldest of any parallel edges is kept
+pub fn component_subgraphs<G>(graph: &G) -> Vec<HashGraph<&G::ID, &G::Weight, &G::Value>>
+    where G: Graph,
+          G::ID: Hash {
+    let nodes = IndexedGraph::new(graph);
+    let (labels, count) = component_labels(&nodes);
+    let mut subgraphs: Vec<HashGraph<&G::ID, &G::Weight, &G::Value>> = (0..count).map(|_| HashGraph::new()).collect();
+
+    for node in graph.nodes() {
+        let label = labels[nodes.index[node.get_id()]];
+        subgraphs[label].add_node_with(node.get_id(), node.get_value())
+            .unwrap_or_else(|_| unreachable!("IDs are unique in the source graph"));
+    }
+    for (u, v, weight) in graph.edges().map(|edge| edge.into_tuple()) {
+        let subgraph = &mut subgraphs[labels[nodes.index[u]]];
+        if !subgraph.contains_edge(&u, &v) {
+            subgraph.add_edge_with(&u, &v, weight)
+                .unwrap_or_else(|_| unreachable!("Both endpoints are in the same component"));
+        }
+    }
+
+    subgraphs
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::algorithms::{weakly_connected_components, component_subgraphs};
+    use crate::directed::Undirected;
+    use crate::{HashGraph, Graph};
+
+    fn sorted(mut components: Vec<Vec<&usize>>) -> Vec<Vec<usize>> {
+        let mut components: Vec<Vec<usize>> = components.iter_mut()
+            .map(|component| {
+                let mut component: Vec<usize> = component.iter().map(|id| **id).collect();
+                component.sort();
+                component
+            })
+            .collect();
+        components.sort();
+        components
+    }
+
+    #[test]
+    fn weak_components_ignore_direction() {
+        let mut g: HashGraph<usize> = HashGraph::new();
+        g.add_nodes(0..6).unwrap();
+        g.add_edge(&1, &0).unwrap();
+        g.add_edge(&1, &2).unwrap();
+        g.add_edge(&4, &3).unwrap();
+
+        assert_eq!(sorted(weakly_connected_components(&g)), vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+
+        let mut u = Undirected::from(HashGraph::<usize>::new());
+        u.add_nodes(0..4).unwrap();
+        u.add_edge(&0, &3).unwrap();
+        assert_eq!(sorted(u.connected_components()), vec![vec![0, 3], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn subgraphs_borrow_the_original() {
+        let mut g: HashGraph<usize, u32, char> = HashGraph::new();
+        for (id, value) in [(0, 'a'), (1, 'b'), (2, 'c')] {
+            g.add_node_with(id, value).unwrap();
+        }
+        g.add_edge_with(&0, &1, 5).unwrap();
+        g.add_edge_with(&1, &0, 6).unwrap();
+
+        let mut subgraphs = component_subgraphs(&g);
+        subgraphs.sort_by_key(|subgraph| subgraph.num_nodes());
+        assert_eq!(subgraphs[0].get(&&2), Some(&&'c'));
+        assert_eq!(subgraphs[1].num_edges(), 2);
+        assert_eq!(subgraphs[1].get_weight(&&1, &&0), Some(&&6));
+    }
+}
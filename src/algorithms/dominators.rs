@@ -0,0 +1,268 @@
+use crate::Graph;
+use crate::algorithms::IndexedGraph;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+const UNREACHABLE: usize = usize::MAX;
+
+///
+/// The dominator tree of the nodes reachable from a root. A node `a` dominates `b` when every path
+/// from the root to `b` goes through `a`, and the immediate dominator of `b` is the closest of its
+/// dominators other than itself
+#[derive(Debug, Clone)]
+pub struct Dominators<'a, ID> {
+    ids: Vec<&'a ID>,
+    index: HashMap<&'a ID, usize>,
+    root: usize,
+    idom: Vec<usize>,
+    frontiers: Vec<Vec<usize>>,
+}
+
+impl<'a, ID> Dominators<'a, ID>
+    where ID: Eq + Hash {
+
+    pub fn root(&self) -> &'a ID {
+        self.ids[self.root]
+    }
+
+    fn reachable_index(&self, node: &ID) -> Option<usize> {
+        self.index.get(node).copied().filter(|&node| self.idom[node] != UNREACHABLE)
+    }
+
+    pub fn is_reachable(&self, node: &ID) -> bool {
+        self.reachable_index(node).is_some()
+    }
+
+    ///
+    /// The closest dominator of `node` other than itself, which is `None` for the root and for
+    /// nodes the root can't reach
+    pub fn immediate_dominator(&self, node: &ID) -> Option<&'a ID> {
+        self.reachable_index(node)
+            .filter(|&node| node != self.root)
+            .map(|node| self.ids[self.idom[node]])
+    }
+
+    ///
+    /// Every dominator of `node`, starting with `node` itself and ending at the root
+    pub fn dominators(&self, node: &ID) -> impl Iterator<Item=&'a ID> + '_ {
+        std::iter::successors(self.reachable_index(node), move |&node| {
+            if node == self.root { None } else { Some(self.idom[node]) }
+        }).map(move |node| self.ids[node])
+    }
+
+    ///
+    /// Whether every path from the root to `b` goes through `a`. Every node dominates itself
+    pub fn dominates(&self, a: &ID, b: &ID) -> bool {
+        match self.index.get(a) {
+            None => false,
+            Some(&a) => self.dominators(b).any(|dominator| self.index[dominator] == a),
+        }
+    }
+
+    ///
+    /// The nodes whose immediate dominator is `node`, which are its children in the dominator tree
+    pub fn immediately_dominated_by(&self, node: &ID) -> impl Iterator<Item=&'a ID> + '_ {
+        let parent = self.reachable_index(node);
+        (0..self.ids.len())
+            .filter(move |&child| child != self.root && Some(self.idom[child]) == parent)
+            .map(move |child| self.ids[child])
+    }
+
+    ///
+    /// The dominance frontier of `node`: the nodes that `node` doesn't strictly dominate but that
+    /// have a predecessor it does dominate
+    pub fn dominance_frontier(&self, node: &ID) -> impl Iterator<Item=&'a ID> + '_ {
+        self.reachable_index(node)
+            .map(|node| self.frontiers[node].as_slice())
+            .unwrap_or(&[])
+            .iter()
+            .map(move |&node| self.ids[node])
+    }
+}
+
+impl<'r, 'a, ID> Dominators<'r, &'a ID>
+    where ID: Eq + Hash {
+
+    ///
+    /// Drops a layer of references, for dominators found on a graph that borrows its IDs from another
+    pub(crate) fn flatten(self) -> Dominators<'a, ID> {
+        let ids: Vec<&'a ID> = self.ids.into_iter().copied().collect();
+        Dominators {
+            index: ids.iter().enumerate().map(|(i, id)| (*id, i)).collect(),
+            ids,
+            root: self.root,
+            idom: self.idom,
+            frontiers: self.frontiers,
+        }
+    }
+}
+
+///
+/// The nodes reachable from `root` in reverse postorder, along with each node's postorder number
+fn reverse_postorder<G: Graph>(nodes: &IndexedGraph<'_, G>, root: usize) -> (Vec<usize>, Vec<usize>) {
+    let mut visited = vec![false; nodes.len()];
+    let mut postorder = vec![UNREACHABLE; nodes.len()];
+    let mut order = vec![];
+    let mut frames = vec![(root, 0)];
+    visited[root] = true;
+
+    while let Some(frame) = frames.last_mut() {
+        let (node, seen) = *frame;
+        if let Some(&next) = nodes.successors[node].get(seen) {
+            frame.1 += 1;
+            if !visited[next] {
+                visited[next] = true;
+                frames.push((next, 0));
+            }
+            continue;
+        }
+        frames.pop();
+        postorder[node] = order.len();
+        order.push(node);
+    }
+
+    order.reverse();
+    (order, postorder)
+}
+
+///
+/// Finds the dominators of every node reachable from `root`, using the iterative algorithm of
+/// Cooper, Harvey and Kennedy. Returns `None` if `root` isn't in the graph
+pub fn dominators<'a, G>(graph: &'a G, root: &G::ID) -> Option<Dominators<'a, G::ID>>
+    where G: Graph,
+          G::ID: Hash {
+    let nodes = IndexedGraph::new(graph);
+    let root = *nodes.index.get(root)?;
+    let (order, postorder) = reverse_postorder(&nodes, root);
+
+    let mut idom = vec![UNREACHABLE; nodes.len()];
+    idom[root] = root;
+    let intersect = |idom: &[usize], mut a: usize, mut b: usize| {
+        while a != b {
+            while postorder[a] < postorder[b] {
+                a = idom[a];
+            }
+            while postorder[b] < postorder[a] {
+                b = idom[b];
+            }
+        }
+        a
+    };
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &node in order.iter().skip(1) {
+            let mut new_idom = UNREACHABLE;
+            for &previous in &nodes.predecessors[node] {
+                if idom[previous] == UNREACHABLE {
+                    continue;
+                }
+                new_idom = if new_idom == UNREACHABLE { previous } else { intersect(&idom, previous, new_idom) };
+            }
+            if idom[node] != new_idom {
+                idom[node] = new_idom;
+                changed = true;
+            }
+        }
+    }
+
+    // Walking up from each predecessor of a join point, everything passed before its immediate
+    // dominator has the join point in its frontier
+    let mut frontiers = vec![vec![]; nodes.len()];
+    for &node in &order {
+        let reachable: Vec<usize> = nodes.predecessors[node].iter()
+            .copied()
+            .filter(|&previous| idom[previous] != UNREACHABLE)
+            .collect();
+        if reachable.len() < 2 {
+            continue;
+        }
+        for previous in reachable {
+            let mut runner = previous;
+            while runner != idom[node] {
+                if frontiers[runner].last() != Some(&node) {
+                    frontiers[runner].push(node);
+                }
+                runner = idom[runner];
+            }
+        }
+    }
+
+    Some(Dominators {
+        ids: nodes.ids,
+        index: nodes.index,
+        root,
+        idom,
+        frontiers,
+    })
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::algorithms::dominators;
+    use crate::directed::{Directed, new_hashed_directed};
+    use crate::HashGraph;
+
+    ///
+    /// An `if` at 1 whose branches 2 and 3 join at 4, which loops back to 1 or exits through 5.
+    /// Node 6 is dead code
+    fn control_flow() -> Directed<u32, (), (), HashGraph<u32>> {
+        let mut g = new_hashed_directed();
+        g.add_nodes(0..7).unwrap();
+        for (u, v) in [(0, 1), (1, 2), (1, 3), (2, 4), (3, 4), (4, 1), (4, 5), (6, 5)] {
+            g.add_edge(&u, &v).unwrap();
+        }
+        g
+    }
+
+    fn sorted<'a>(ids: impl Iterator<Item=&'a u32>) -> Vec<u32> {
+        let mut ids: Vec<u32> = ids.copied().collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn immediate_dominators() {
+        let g = control_flow();
+        let tree = g.dominators(&0).unwrap();
+        assert_eq!(tree.root(), &0);
+        assert_eq!(tree.immediate_dominator(&0), None);
+        assert_eq!(tree.immediate_dominator(&2), Some(&1));
+        assert_eq!(tree.immediate_dominator(&4), Some(&1));
+        assert_eq!(tree.immediate_dominator(&5), Some(&4));
+        assert_eq!(tree.immediate_dominator(&6), None);
+        assert!(!tree.is_reachable(&6));
+
+        assert_eq!(tree.dominators(&5).copied().collect::<Vec<_>>(), vec![5, 4, 1, 0]);
+        assert!(tree.dominates(&1, &3));
+        assert!(tree.dominates(&3, &3));
+        assert!(!tree.dominates(&2, &4));
+        assert_eq!(sorted(tree.immediately_dominated_by(&1)), vec![2, 3, 4]);
+
+        assert!(dominators(&*g, &10).is_none());
+    }
+
+    #[test]
+    fn frontiers() {
+        let g = control_flow();
+        let tree = g.dominators(&0).unwrap();
+        assert_eq!(sorted(tree.dominance_frontier(&2)), vec![4]);
+        assert_eq!(sorted(tree.dominance_frontier(&4)), vec![1]);
+        assert_eq!(sorted(tree.dominance_frontier(&1)), vec![1]);
+        assert_eq!(sorted(tree.dominance_frontier(&0)), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn post_dominators() {
+        let g = control_flow();
+        let tree = g.post_dominators(&5).unwrap();
+        assert_eq!(tree.root(), &5);
+        assert_eq!(tree.immediate_dominator(&2), Some(&4));
+        assert_eq!(tree.immediate_dominator(&1), Some(&4));
+        assert_eq!(tree.immediate_dominator(&6), Some(&5));
+        assert!(tree.dominates(&4, &0));
+        assert_eq!(sorted(tree.dominance_frontier(&2)), vec![1]);
+    }
+}
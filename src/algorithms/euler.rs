@@ -0,0 +1,250 @@
+use crate::{Graph, EdgeId};
+use crate::algorithms::IndexedGraph;
+use std::fmt::{Debug, Display, Formatter, Result};
+use std::hash::Hash;
+
+///
+/// One step of an Eulerian trail: the node it leaves, the node it reaches and the edge it uses
+pub type EulerStep<'a, ID> = (&'a ID, &'a ID, EdgeId);
+
+///
+/// The reason a graph has no Eulerian trail or circuit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EulerError<ID> {
+    ///
+    /// These nodes have more edges going in than out, or the other way around, or for an undirected
+    /// graph an odd number of edges. A circuit allows none of them and a trail allows two
+    Unbalanced(Vec<ID>),
+    ///
+    /// The edges aren't all connected: the trail from the first node can't reach the second
+    Disconnected(ID, ID),
+}
+
+impl<ID: Debug> Display for EulerError<ID> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            EulerError::Unbalanced(nodes) => write!(f, "No Eulerian trail exists: the degrees of {:?} are unbalanced", nodes),
+            EulerError::Disconnected(u, v) => write!(f, "No Eulerian trail exists: the edges at {:?} and {:?} aren't connected", u, v),
+        }
+    }
+}
+
+impl<ID: Debug> std::error::Error for EulerError<ID> {}
+
+///
+/// The graph's edges as `(source, target, ID)`, and for each node the edges it can leave through
+struct Edges {
+    directed: bool,
+    ends: Vec<(usize, usize, EdgeId)>,
+    leaving: Vec<Vec<(usize, usize)>>,
+    /// In degree minus out degree for a directed graph, or degree modulo 2 for an undirected one
+    imbalance: Vec<isize>,
+}
+
+impl Edges {
+
+    fn new<G: Graph>(graph: &G, nodes: &IndexedGraph<'_, G>, directed: bool) -> Self
+        where G::ID: Hash {
+        let mut ends = vec![];
+        let mut leaving = vec![vec![]; nodes.len()];
+        let mut imbalance = vec![0; nodes.len()];
+        for edge in graph.edges() {
+            let (u, v) = (nodes.index[edge.get_source()], nodes.index[edge.get_target()]);
+            leaving[u].push((v, ends.len()));
+            if directed {
+                imbalance[u] -= 1;
+                imbalance[v] += 1;
+            } else if u != v {
+                leaving[v].push((u, ends.len()));
+                imbalance[u] ^= 1;
+                imbalance[v] ^= 1;
+            }
+            ends.push((u, v, edge.get_id()));
+        }
+        Edges { directed, ends, leaving, imbalance }
+    }
+
+    ///
+    /// Picks where the trail has to start: the node with a spare edge to leave through if there is
+    /// one, or else the first node with any edges. Gives back the unbalanced nodes if there's no trail
+    fn start(&self, circuit: bool) -> std::result::Result<Option<usize>, Vec<usize>> {
+        let unbalanced: Vec<usize> = (0..self.leaving.len()).filter(|&node| self.imbalance[node] != 0).collect();
+        let allowed = match unbalanced.as_slice() {
+            [] => true,
+            [u, v] if !circuit => !self.directed || self.imbalance[*u] * self.imbalance[*v] == -1,
+            _ => false,
+        };
+        if !allowed {
+            return Err(unbalanced);
+        }
+
+        Ok(unbalanced.into_iter()
+            .find(|&node| !self.directed || self.imbalance[node] < 0)
+            .or_else(|| (0..self.leaving.len()).find(|&node| !self.leaving[node].is_empty())))
+    }
+}
+
+///
+/// Hierholzer's algorithm, splicing in detours as the trail gets stuck. Returns the steps as
+/// `(from, to, edge)` in order, which uses every edge only if they are all connected to `start`
+fn hierholzer(edges: &Edges, start: usize) -> Vec<(usize, usize, usize)> {
+    let mut used = vec![false; edges.ends.len()];
+    let mut next_edge = vec![0; edges.leaving.len()];
+    let mut stack = vec![(start, None)];
+    let mut trail = Vec::with_capacity(edges.ends.len());
+
+    while let Some(&(node, arrived_by)) = stack.last() {
+        match edges.leaving[node].get(next_edge[node]) {
+            Some(&(next, edge)) => {
+                next_edge[node] += 1;
+                if !used[edge] {
+                    used[edge] = true;
+                    stack.push((next, Some((node, edge))));
+                }
+            },
+            None => {
+                stack.pop();
+                if let Some((previous, edge)) = arrived_by {
+                    trail.push((previous, node, edge));
+                }
+            },
+        }
+    }
+
+    trail.reverse();
+    trail
+}
+
+fn eulerian<'a, G>(graph: &'a G, directed: bool, circuit: bool) -> std::result::Result<Vec<EulerStep<'a, G::ID>>, EulerError<&'a G::ID>>
+    where G: Graph,
+          G::ID: Hash {
+    let nodes = IndexedGraph::new(graph);
+    let edges = Edges::new(graph, &nodes, directed);
+    let start = match edges.start(circuit) {
+        Ok(Some(start)) => start,
+        Ok(None) => return Ok(vec![]),
+        Err(unbalanced) => return Err(EulerError::Unbalanced(unbalanced.into_iter().map(|node| nodes.id(node)).collect())),
+    };
+
+    let trail = hierholzer(&edges, start);
+    if trail.len() < edges.ends.len() {
+        let mut used = vec![false; edges.ends.len()];
+        for &(_, _, edge) in &trail {
+            used[edge] = true;
+        }
+        let missed = (0..edges.ends.len()).find(|&edge| !used[edge]).expect("Some edge wasn't used");
+        return Err(EulerError::Disconnected(nodes.id(start), nodes.id(edges.ends[missed].0)));
+    }
+
+    Ok(trail.into_iter()
+        .map(|(u, v, edge)| (nodes.id(u), nodes.id(v), edges.ends[edge].2))
+        .collect())
+}
+
+///
+/// Finds a trail that follows every edge exactly once in its direction
+pub fn eulerian_trail<G>(graph: &G) -> std::result::Result<Vec<EulerStep<'_, G::ID>>, EulerError<&G::ID>>
+    where G: Graph,
+          G::ID: Hash {
+    eulerian(graph, true, false)
+}
+
+///
+/// Finds a trail that follows every edge exactly once in its direction and ends where it starts
+pub fn eulerian_circuit<G>(graph: &G) -> std::result::Result<Vec<EulerStep<'_, G::ID>>, EulerError<&G::ID>>
+    where G: Graph,
+          G::ID: Hash {
+    eulerian(graph, true, true)
+}
+
+///
+/// Finds a trail that uses every edge of [`Graph::edges`] exactly once, going either way along it.
+/// Each step gives the ID of the edge it uses, even when it goes from that edge's target to its source
+pub fn undirected_eulerian_trail<G>(graph: &G) -> std::result::Result<Vec<EulerStep<'_, G::ID>>, EulerError<&G::ID>>
+    where G: Graph,
+          G::ID: Hash {
+    eulerian(graph, false, false)
+}
+
+///
+/// Like [`undirected_eulerian_trail`], but ending where it starts
+pub fn undirected_eulerian_circuit<G>(graph: &G) -> std::result::Result<Vec<EulerStep<'_, G::ID>>, EulerError<&G::ID>>
+    where G: Graph,
+          G::ID: Hash {
+    eulerian(graph, false, true)
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::algorithms::{EulerError, EulerStep, eulerian_circuit};
+    use crate::directed::{new_hashed_directed, new_hashed_undirected};
+    use crate::Graph;
+    use std::collections::HashSet;
+
+    fn check_trail<G: Graph<ID=u32>>(graph: &G, trail: &[EulerStep<'_, u32>]) {
+        assert_eq!(trail.len(), graph.num_edges());
+        assert_eq!(trail.iter().map(|(_, _, edge)| *edge).collect::<HashSet<_>>().len(), trail.len());
+        for window in trail.windows(2) {
+            assert_eq!(window[0].1, window[1].0);
+        }
+        for (u, v, edge) in trail {
+            let edge = graph.get_edge(*edge).unwrap();
+            let ends = (edge.get_source(), edge.get_target());
+            assert!(ends == (*u, *v) || ends == (*v, *u));
+        }
+    }
+
+    #[test]
+    fn directed() {
+        let mut g = new_hashed_directed::<u32, (), ()>();
+        g.add_nodes(0..4).unwrap();
+        for (u, v) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 2)] {
+            g.add_edge(&u, &v).unwrap();
+        }
+        let circuit = g.eulerian_circuit().unwrap();
+        check_trail(&*g, &circuit);
+        assert_eq!(circuit.first().unwrap().0, circuit.last().unwrap().1);
+
+        g.remove_edge(&3, &2).unwrap();
+        match eulerian_circuit(&*g) {
+            Err(EulerError::Unbalanced(mut nodes)) => {
+                nodes.sort();
+                assert_eq!(nodes, vec![&2, &3]);
+            },
+            other => panic!("Expected unbalanced degrees, got {:?}", other),
+        }
+        let trail = g.eulerian_trail().unwrap();
+        check_trail(&*g, &trail);
+        assert_eq!(trail[0].0, &2);
+        assert_eq!(trail.last().unwrap().1, &3);
+    }
+
+    #[test]
+    fn undirected() {
+        // A house: a square with a roof, drawn without lifting the pen from a bottom corner
+        let mut g = new_hashed_undirected::<u32, (), ()>();
+        g.add_nodes(0..5).unwrap();
+        for (u, v) in [(0, 1), (1, 2), (2, 3), (3, 0), (0, 2), (1, 3), (2, 4), (3, 4)] {
+            g.add_edge(&u, &v).unwrap();
+        }
+        let trail = g.eulerian_trail().unwrap();
+        check_trail(&g, &trail);
+        let ends: HashSet<u32> = [*trail[0].0, *trail.last().unwrap().1].iter().copied().collect();
+        assert_eq!(ends, [0, 1].iter().copied().collect());
+        assert!(matches!(g.eulerian_circuit(), Err(EulerError::Unbalanced(_))));
+
+        let mut square = new_hashed_undirected::<u32, (), ()>();
+        square.add_nodes(0..8).unwrap();
+        for (u, v) in [(0, 1), (1, 2), (2, 3), (3, 0), (4, 5), (5, 6), (6, 4)] {
+            square.add_edge(&u, &v).unwrap();
+        }
+        assert!(matches!(square.eulerian_circuit(), Err(EulerError::Disconnected(_, _))));
+        square.remove_edge(&4, &5).unwrap();
+        square.remove_edge(&5, &6).unwrap();
+        square.remove_edge(&6, &4).unwrap();
+        check_trail(&square, &square.eulerian_circuit().unwrap());
+
+        assert_eq!(new_hashed_undirected::<u32, (), ()>().eulerian_circuit(), Ok(vec![]));
+    }
+}
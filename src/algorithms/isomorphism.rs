@@ -0,0 +1,438 @@
+use crate::Graph;
+use crate::algorithms::IndexedGraph;
+use std::hash::Hash;
+
+///
+/// How the nodes of the first graph have to map onto the second
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Mode {
+    ///
+    /// Both graphs have exactly the same structure
+    Isomorphism,
+    ///
+    /// The first graph is the subgraph induced by the nodes it maps to, so edges between those
+    /// nodes in the second graph have to be in the first
+    Induced,
+    ///
+    /// Every edge of the first graph maps to an edge of the second, which can have extra edges
+    Monomorphism,
+}
+
+impl Mode {
+    ///
+    /// Whether `count` things in the first graph can map onto `other` things in the second
+    fn fits(self, count: usize, other: usize) -> bool {
+        match self {
+            Mode::Isomorphism => count == other,
+            Mode::Induced | Mode::Monomorphism => count <= other,
+        }
+    }
+}
+
+///
+/// A VF2 search for node mappings between two graphs.
+///
+/// Nodes of `g1` are matched in an order where each node is as connected as possible to the nodes
+/// before it, and a pair of nodes is only mapped if every edge between them and the nodes mapped so
+/// far has a counterpart. The search is resumable, so every mapping can be found one at a time
+pub(crate) struct Vf2<'a, 'b, G1: Graph, G2: Graph, NM, EM> {
+    g1: &'a G1,
+    g2: &'b G2,
+    nodes1: IndexedGraph<'a, G1>,
+    nodes2: IndexedGraph<'b, G2>,
+    order: Vec<usize>,
+    core1: Vec<Option<usize>>,
+    core2: Vec<Option<usize>>,
+    ///
+    /// The `g2` nodes to try at each depth of the search, and how many have been tried
+    candidates: Vec<Vec<usize>>,
+    cursors: Vec<usize>,
+    started: bool,
+    done: bool,
+    mode: Mode,
+    node_match: NM,
+    edge_match: EM,
+}
+
+impl<'a, 'b, G1, G2, NM, EM> Vf2<'a, 'b, G1, G2, NM, EM>
+    where G1: Graph,
+          G2: Graph,
+          G1::ID: Hash,
+          G2::ID: Hash,
+          NM: FnMut(&G1::Value, &G2::Value) -> bool,
+          EM: FnMut(&G1::Weight, &G2::Weight) -> bool {
+
+    pub(crate) fn new(g1: &'a G1, g2: &'b G2, mode: Mode, node_match: NM, edge_match: EM) -> Self {
+        let nodes1 = IndexedGraph::new(g1);
+        let nodes2 = IndexedGraph::new(g2);
+        let order = match_order(&nodes1);
+        let done = !mode.fits(nodes1.len(), nodes2.len()) || !mode.fits(g1.num_edges(), g2.num_edges());
+        Vf2 {
+            g1,
+            g2,
+            core1: vec![None; nodes1.len()],
+            core2: vec![None; nodes2.len()],
+            nodes1,
+            nodes2,
+            order,
+            candidates: vec![],
+            cursors: vec![],
+            started: false,
+            done,
+            mode,
+            node_match,
+            edge_match,
+        }
+    }
+
+    ///
+    /// Finds the next mapping, returning it as `(g1 ID, g2 ID)` pairs in `g1`'s node order
+    pub(crate) fn next_mapping(&mut self) -> Option<Vec<(&'a G1::ID, &'b G2::ID)>> {
+        if !self.advance() {
+            return None;
+        }
+        let mapping = self.core1.iter()
+            .enumerate()
+            .map(|(n1, n2)| (self.nodes1.id(n1), self.nodes2.id(n2.expect("Every node is mapped"))))
+            .collect();
+        Some(mapping)
+    }
+
+    fn advance(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        if !self.started {
+            self.started = true;
+            if self.order.is_empty() {
+                self.done = true;
+                return true;
+            }
+            self.descend();
+        } else {
+            // Carry on from the last full mapping by undoing its final pair
+            let last = self.order[self.cursors.len() - 1];
+            self.unassign(last);
+        }
+
+        loop {
+            let depth = self.cursors.len() - 1;
+            let n1 = self.order[depth];
+            let mut found = None;
+            while self.cursors[depth] < self.candidates[depth].len() {
+                let n2 = self.candidates[depth][self.cursors[depth]];
+                self.cursors[depth] += 1;
+                if self.core2[n2].is_none() && self.feasible(n1, n2) {
+                    found = Some(n2);
+                    break;
+                }
+            }
+
+            match found {
+                Some(n2) => {
+                    self.core1[n1] = Some(n2);
+                    self.core2[n2] = Some(n1);
+                    if depth + 1 == self.order.len() {
+                        return true;
+                    }
+                    self.descend();
+                },
+                None => {
+                    self.cursors.pop();
+                    self.candidates.pop();
+                    if self.cursors.is_empty() {
+                        self.done = true;
+                        return false;
+                    }
+                    let previous = self.order[self.cursors.len() - 1];
+                    self.unassign(previous);
+                },
+            }
+        }
+    }
+
+    ///
+    /// Moves the search on to the next node of `g1`
+    fn descend(&mut self) {
+        let n1 = self.order[self.cursors.len()];
+        let candidates = self.candidates_for(n1);
+        self.candidates.push(candidates);
+        self.cursors.push(0);
+    }
+
+    ///
+    /// The `g2` nodes that `n1` could be mapped to. When `n1` has an edge to or from a mapped node,
+    /// only the nodes with the same edge to or from that node's image can work, so every node of
+    /// `g2` is only tried for the first node of each component of `g1`
+    fn candidates_for(&self, n1: usize) -> Vec<usize> {
+        let through_successors = self.nodes1.successors[n1].iter()
+            .filter_map(|&m1| self.core1[m1])
+            .map(|m2| &self.nodes2.predecessors[m2]);
+        let through_predecessors = self.nodes1.predecessors[n1].iter()
+            .filter_map(|&m1| self.core1[m1])
+            .map(|m2| &self.nodes2.successors[m2]);
+        match through_successors.chain(through_predecessors).min_by_key(|options| options.len()) {
+            Some(options) => options.to_vec(),
+            None => (0..self.nodes2.len()).collect(),
+        }
+    }
+
+    fn unassign(&mut self, n1: usize) {
+        if let Some(n2) = self.core1[n1].take() {
+            self.core2[n2] = None;
+        }
+    }
+
+    fn feasible(&mut self, n1: usize, n2: usize) -> bool {
+        let (g1, g2) = (self.g1, self.g2);
+        let value1 = g1.get(self.nodes1.id(n1)).expect("Indexed nodes exist");
+        let value2 = g2.get(self.nodes2.id(n2)).expect("Indexed nodes exist");
+        if !(self.node_match)(value1, value2) {
+            return false;
+        }
+
+        let (successors1, predecessors1) = (&self.nodes1.successors[n1], &self.nodes1.predecessors[n1]);
+        let (successors2, predecessors2) = (&self.nodes2.successors[n2], &self.nodes2.predecessors[n2]);
+        if !self.mode.fits(successors1.len(), successors2.len()) || !self.mode.fits(predecessors1.len(), predecessors2.len()) {
+            return false;
+        }
+
+        let loop1 = successors1.contains(&n1);
+        let loop2 = successors2.contains(&n2);
+        if (loop1 && !loop2) || (loop2 && !loop1 && self.mode != Mode::Monomorphism) {
+            return false;
+        }
+
+        let mut pairs = vec![];
+        if loop1 {
+            pairs.push(((n1, n1), (n2, n2)));
+        }
+        for &m1 in successors1 {
+            if let Some(m2) = self.core1[m1] {
+                if !successors2.contains(&m2) {
+                    return false;
+                }
+                pairs.push(((n1, m1), (n2, m2)));
+            }
+        }
+        for &m1 in predecessors1 {
+            if let Some(m2) = self.core1[m1] {
+                if !predecessors2.contains(&m2) {
+                    return false;
+                }
+                pairs.push(((m1, n1), (m2, n2)));
+            }
+        }
+
+        // Unless `g2` is allowed extra edges, it can't have edges between the pair and the mapped
+        // nodes that `g1` doesn't
+        if self.mode == Mode::Monomorphism {
+            return pairs.into_iter().all(|((u1, v1), (u2, v2))| self.edges_match(u1, v1, u2, v2));
+        }
+        let extra_successor = successors2.iter()
+            .any(|&m2| self.core2[m2].is_some_and(|m1| !successors1.contains(&m1)));
+        let extra_predecessor = predecessors2.iter()
+            .any(|&m2| self.core2[m2].is_some_and(|m1| !predecessors1.contains(&m1)));
+        if extra_successor || extra_predecessor {
+            return false;
+        }
+
+        pairs.into_iter().all(|((u1, v1), (u2, v2))| self.edges_match(u1, v1, u2, v2))
+    }
+
+    ///
+    /// Checks that each `u1 -> v1` edge can be paired up with its own `u2 -> v2` edge
+    fn edges_match(&mut self, u1: usize, v1: usize, u2: usize, v2: usize) -> bool {
+        let (g1, g2) = (self.g1, self.g2);
+        let weights1: Vec<&G1::Weight> = g1.get_weights(self.nodes1.id(u1), self.nodes1.id(v1)).collect();
+        let weights2: Vec<&G2::Weight> = g2.get_weights(self.nodes2.id(u2), self.nodes2.id(v2)).collect();
+        if !self.mode.fits(weights1.len(), weights2.len()) {
+            return false;
+        }
+
+        let edge_match = &mut self.edge_match;
+        let options: Vec<Vec<usize>> = weights1.iter()
+            .map(|w1| (0..weights2.len()).filter(|&j| edge_match(w1, weights2[j])).collect())
+            .collect();
+        match_all(&options, weights2.len())
+    }
+}
+
+///
+/// Whether every item on the left can be given its own item on the right, where `options[i]` lists
+/// the right items that left item `i` can take. Looks for augmenting paths, so it doesn't matter
+/// which options are tried first
+fn match_all(options: &[Vec<usize>], right: usize) -> bool {
+    fn augment(left: usize, options: &[Vec<usize>], seen: &mut [bool], owner: &mut [Option<usize>]) -> bool {
+        for &j in &options[left] {
+            if seen[j] {
+                continue;
+            }
+            seen[j] = true;
+            if owner[j].is_none_or(|other| augment(other, options, seen, owner)) {
+                owner[j] = Some(left);
+                return true;
+            }
+        }
+        false
+    }
+
+    let mut owner = vec![None; right];
+    (0..options.len()).all(|left| augment(left, options, &mut vec![false; right], &mut owner))
+}
+
+///
+/// Orders the nodes so that each one has as many edges as possible to the nodes before it, which
+/// lets the search reject bad pairs early
+fn match_order<G: Graph>(nodes: &IndexedGraph<'_, G>) -> Vec<usize> {
+    let degree = |node: usize| nodes.successors[node].len() + nodes.predecessors[node].len();
+    let mut ordered = vec![false; nodes.len()];
+    let mut links = vec![0usize; nodes.len()];
+    let mut order = Vec::with_capacity(nodes.len());
+
+    while order.len() < nodes.len() {
+        let next = (0..nodes.len())
+            .filter(|&node| !ordered[node])
+            .max_by_key(|&node| (links[node], degree(node)))
+            .expect("There is an unordered node left");
+        ordered[next] = true;
+        order.push(next);
+        for &neighbor in nodes.successors[next].iter().chain(&nodes.predecessors[next]) {
+            links[neighbor] += 1;
+        }
+    }
+
+    order
+}
+
+///
+/// Finds a mapping from the nodes of `g1` to the nodes of `g2` that carries every edge of `g1` to
+/// an edge of `g2` and vice versa, using `node_match` and `edge_match` to decide whether node values
+/// and edge weights correspond
+pub fn find_isomorphism_by<'a, 'b, G1, G2, NM, EM>(g1: &'a G1, g2: &'b G2, node_match: NM, edge_match: EM) -> Option<Vec<(&'a G1::ID, &'b G2::ID)>>
+    where G1: Graph,
+          G2: Graph,
+          G1::ID: Hash,
+          G2::ID: Hash,
+          NM: FnMut(&G1::Value, &G2::Value) -> bool,
+          EM: FnMut(&G1::Weight, &G2::Weight) -> bool {
+    Vf2::new(g1, g2, Mode::Isomorphism, node_match, edge_match).next_mapping()
+}
+
+///
+/// Finds an isomorphism between `g1` and `g2` that keeps node values and edge weights equal,
+/// returned as `(g1 ID, g2 ID)` pairs
+pub fn find_isomorphism<'a, 'b, G1, G2>(g1: &'a G1, g2: &'b G2) -> Option<Vec<(&'a G1::ID, &'b G2::ID)>>
+    where G1: Graph,
+          G2: Graph,
+          G1::ID: Hash,
+          G2::ID: Hash,
+          G1::Value: PartialEq<G2::Value>,
+          G1::Weight: PartialEq<G2::Weight> {
+    find_isomorphism_by(g1, g2, |v1, v2| v1 == v2, |w1, w2| w1 == w2)
+}
+
+pub fn is_isomorphic<G1, G2>(g1: &G1, g2: &G2) -> bool
+    where G1: Graph,
+          G2: Graph,
+          G1::ID: Hash,
+          G2::ID: Hash,
+          G1::Value: PartialEq<G2::Value>,
+          G1::Weight: PartialEq<G2::Weight> {
+    find_isomorphism(g1, g2).is_some()
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::algorithms::{find_isomorphism, find_isomorphism_by, is_isomorphic};
+    use crate::directed::Undirected;
+    use crate::{HashGraph, HashMultiGraph, Graph};
+
+    fn cycle(ids: &[char]) -> HashGraph<char, u32> {
+        let mut g = HashGraph::new();
+        for id in ids {
+            g.add_node(*id).unwrap();
+        }
+        for (i, id) in ids.iter().enumerate() {
+            g.add_edge_with(id, &ids[(i + 1) % ids.len()], i as u32).unwrap();
+        }
+        g
+    }
+
+    #[test]
+    fn relabelled_cycles() {
+        let g1 = cycle(&['a', 'b', 'c', 'd']);
+        let g2 = cycle(&['w', 'x', 'y', 'z']);
+        let mapping = find_isomorphism(&g1, &g2).unwrap();
+        for (u, v) in mapping {
+            let expected = (b'w' + (*u as u8 - b'a')) as char;
+            assert_eq!(*v, expected, "Weights pin down the rotation");
+        }
+
+        let rotated = find_isomorphism_by(&g1, &g2, |_, _| true, |_, _| true);
+        assert!(rotated.is_some());
+        assert!(!is_isomorphic(&g1, &cycle(&['a', 'b', 'c'])));
+    }
+
+    #[test]
+    fn direction_values_and_loops() {
+        let mut path: HashGraph<usize> = HashGraph::new();
+        path.add_nodes(0..3).unwrap();
+        path.add_edge(&0, &1).unwrap();
+        path.add_edge(&1, &2).unwrap();
+
+        let mut star: HashGraph<usize> = HashGraph::new();
+        star.add_nodes(0..3).unwrap();
+        star.add_edge(&0, &1).unwrap();
+        star.add_edge(&0, &2).unwrap();
+        assert!(!is_isomorphic(&path, &star));
+
+        let mut looped = path.clone();
+        looped.add_edge(&2, &2).unwrap();
+        let mut other = path.clone();
+        other.add_edge(&0, &0).unwrap();
+        assert!(!is_isomorphic(&looped, &other), "Only the last node of the path has a loop");
+
+        let mut valued: HashGraph<usize, (), char> = HashGraph::new();
+        valued.add_node_with(0, 'x').unwrap();
+        let mut other_valued: HashGraph<usize, (), char> = HashGraph::new();
+        other_valued.add_node_with(5, 'y').unwrap();
+        assert!(!is_isomorphic(&valued, &other_valued));
+    }
+
+    #[test]
+    fn parallel_edges_and_undirected() {
+        let mut g1: HashMultiGraph<usize, u8> = HashMultiGraph::new();
+        g1.add_nodes(0..2).unwrap();
+        g1.add_edge_with(&0, &1, 1).unwrap();
+        g1.add_edge_with(&0, &1, 2).unwrap();
+
+        let mut g2: HashMultiGraph<usize, u8> = HashMultiGraph::new();
+        g2.add_nodes(0..2).unwrap();
+        g2.add_edge_with(&1, &0, 2).unwrap();
+        g2.add_edge_with(&1, &0, 1).unwrap();
+        let mut mapping = find_isomorphism(&g1, &g2).unwrap();
+        mapping.sort();
+        assert_eq!(mapping, vec![(&0, &1), (&1, &0)]);
+
+        // The first weight that fits isn't always the right one to pair with
+        let at_most = find_isomorphism_by(&g1, &g2, |_, _| true, |w1, w2| w1 <= w2);
+        assert!(at_most.is_some());
+        let mut heavier = g2.clone();
+        heavier.add_edge_with(&1, &0, 0).unwrap();
+        g1.add_edge_with(&0, &1, 3).unwrap();
+        assert!(find_isomorphism_by(&g1, &heavier, |_, _| true, |w1, w2| w1 <= w2).is_none());
+
+        let mut u1 = Undirected::from(HashGraph::<usize>::new());
+        let mut u2 = Undirected::from(HashGraph::<usize>::new());
+        for g in [&mut u1, &mut u2] {
+            g.add_nodes(0..3).unwrap();
+        }
+        u1.add_edge(&0, &1).unwrap();
+        u1.add_edge(&1, &2).unwrap();
+        u2.add_edge(&2, &0).unwrap();
+        u2.add_edge(&0, &1).unwrap();
+        assert!(is_isomorphic(&u1, &u2));
+    }
+}
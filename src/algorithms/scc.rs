@@ -0,0 +1,175 @@
+use crate::{Graph, HashGraph};
+use crate::algorithms::IndexedGraph;
+use std::hash::Hash;
+
+///
+/// Tarjan's algorithm, returning the component of each node. Components are numbered so that
+/// every edge between two components goes from a lower number to a higher one
+pub(crate) fn component_labels<G: Graph>(nodes: &IndexedGraph<'_, G>) -> (Vec<usize>, usize) {
+    const UNVISITED: usize = usize::MAX;
+    let mut order = vec![UNVISITED; nodes.len()];
+    let mut low_link = vec![0; nodes.len()];
+    let mut on_stack = vec![false; nodes.len()];
+    let mut stack = vec![];
+    let mut labels = vec![UNVISITED; nodes.len()];
+    let mut count = 0;
+    let mut next_order = 0;
+
+    for root in 0..nodes.len() {
+        if order[root] != UNVISITED {
+            continue;
+        }
+
+        // Each frame is a node and how many of its successors have been looked at
+        let mut frames = vec![(root, 0)];
+        order[root] = next_order;
+        low_link[root] = next_order;
+        next_order += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(frame) = frames.last_mut() {
+            let (node, visited) = *frame;
+            if let Some(&next) = nodes.successors[node].get(visited) {
+                frame.1 += 1;
+                if order[next] == UNVISITED {
+                    order[next] = next_order;
+                    low_link[next] = next_order;
+                    next_order += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    frames.push((next, 0));
+                } else if on_stack[next] {
+                    low_link[node] = low_link[node].min(order[next]);
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some(&(parent, _)) = frames.last() {
+                low_link[parent] = low_link[parent].min(low_link[node]);
+            }
+            if low_link[node] == order[node] {
+                loop {
+                    let member = stack.pop().expect("The node is on the stack");
+                    on_stack[member] = false;
+                    labels[member] = count;
+                    if member == node {
+                        break;
+                    }
+                }
+                count += 1;
+            }
+        }
+    }
+
+    // Tarjan finds sink components first, so flip the numbering to put sources first
+    for label in labels.iter_mut() {
+        *label = count - 1 - *label;
+    }
+    (labels, count)
+}
+
+///
+/// Groups the nodes into strongly connected components, where every node can reach every other.
+/// The components are in topological order, so no edge goes from a component to an earlier one
+pub fn strongly_connected_components<G>(graph: &G) -> Vec<Vec<&G::ID>>
+    where G: Graph,
+          G::ID: Hash {
+    let nodes = IndexedGraph::new(graph);
+    let (labels, count) = component_labels(&nodes);
+    let mut components = vec![vec![]; count];
+    for (node, label) in labels.into_iter().enumerate() {
+        components[label].push(nodes.id(node));
+    }
+    components
+}
+
+///
+/// Collapses each strongly connected component into a single node, giving a graph with no cycles.
+///
+/// Node `i` of the result is the `i`-th component of [`strongly_connected_components`] and its value
+/// lists the IDs in that component. There is an edge between two components when the original
+/// graph has any edge between their members
+pub fn condensation<G>(graph: &G) -> HashGraph<usize, (), Vec<G::ID>>
+    where G: Graph,
+          G::ID: Hash + Clone {
+    let nodes = IndexedGraph::new(graph);
+    let (labels, count) = component_labels(&nodes);
+    let mut members = vec![vec![]; count];
+    for (node, &label) in labels.iter().enumerate() {
+        members[label].push(nodes.id(node).clone());
+    }
+
+    let mut output = HashGraph::new();
+    for (label, ids) in members.into_iter().enumerate() {
+        output.add_node_with(label, ids)
+            .unwrap_or_else(|_| unreachable!("Component labels are unique"));
+    }
+    for (u, successors) in nodes.successors.iter().enumerate() {
+        for &v in successors {
+            let (from, to) = (labels[u], labels[v]);
+            if from != to && !output.contains_edge(&from, &to) {
+                output.add_edge(&from, &to)
+                    .unwrap_or_else(|_| unreachable!("Both components exist"));
+            }
+        }
+    }
+
+    output
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::algorithms::{strongly_connected_components, condensation};
+    use crate::directed::Directed;
+    use crate::{HashGraph, Graph};
+
+    fn modules() -> Directed<&'static str, (), (), HashGraph<&'static str>> {
+        let mut g = Directed::from(HashGraph::new());
+        for id in ["app", "ui", "theme", "core", "util", "log"] {
+            g.add_node(id).unwrap();
+        }
+        for (u, v) in [("app", "ui"), ("ui", "theme"), ("theme", "ui"), ("ui", "core"),
+                       ("core", "util"), ("util", "core"), ("util", "log")] {
+            g.add_edge(&u, &v).unwrap();
+        }
+        g
+    }
+
+    #[test]
+    fn components_in_topological_order() {
+        let g = modules();
+        let components: Vec<Vec<&str>> = strongly_connected_components(&*g)
+            .into_iter()
+            .map(|component| {
+                let mut component: Vec<&str> = component.into_iter().copied().collect();
+                component.sort();
+                component
+            })
+            .collect();
+        assert_eq!(components, vec![vec!["app"], vec!["theme", "ui"], vec!["core", "util"], vec!["log"]]);
+        assert_eq!(g.strongly_connected_components().len(), 4);
+    }
+
+    #[test]
+    fn condensation_is_a_dag() {
+        let condensed = modules().condensation();
+        assert_eq!(condensed.num_nodes(), 4);
+        assert_eq!(condensed.num_edges(), 3);
+        for edge in condensed.edges() {
+            assert!(edge.get_source() < edge.get_target());
+        }
+        let mut ui = condensed.get(&1).unwrap().clone();
+        ui.sort();
+        assert_eq!(ui, vec!["theme", "ui"]);
+
+        let mut deep = HashGraph::<usize>::new();
+        deep.add_nodes(0..100_000).unwrap();
+        for i in 0..100_000 {
+            deep.add_edge(&i, &((i + 1) % 100_000)).unwrap();
+        }
+        assert_eq!(condensation(&deep).num_nodes(), 1, "Long cycles don't overflow the stack");
+    }
+}
@@ -0,0 +1,233 @@
+use crate::Graph;
+use crate::algorithms::isomorphism::{Vf2, Mode};
+use std::hash::Hash;
+
+///
+/// Searches a target graph for every place a smaller pattern graph occurs.
+///
+/// By default a match is a monomorphism: each pattern node maps to its own target node and each
+/// pattern edge to an edge between the matching target nodes, while the target can have extra edges.
+/// Any node value and edge weight matches unless predicates are given
+pub struct SubgraphMatcher<'a, 'b, P, G, NM = fn(&<P as Graph>::Value, &<G as Graph>::Value) -> bool, EM = fn(&<P as Graph>::Weight, &<G as Graph>::Weight) -> bool>
+    where P: Graph,
+          G: Graph {
+    pattern: &'a P,
+    target: &'b G,
+    induced: bool,
+    limit: Option<usize>,
+    node_match: NM,
+    edge_match: EM,
+}
+
+impl<'a, 'b, P, G> SubgraphMatcher<'a, 'b, P, G>
+    where P: Graph,
+          G: Graph {
+
+    pub fn new(pattern: &'a P, target: &'b G) -> Self {
+        SubgraphMatcher {
+            pattern,
+            target,
+            induced: false,
+            limit: None,
+            node_match: |_, _| true,
+            edge_match: |_, _| true,
+        }
+    }
+}
+
+impl<'a, 'b, P, G, NM, EM> SubgraphMatcher<'a, 'b, P, G, NM, EM>
+    where P: Graph,
+          G: Graph {
+
+    ///
+    /// Only match where the target has no edges between the matched nodes beyond the pattern's
+    pub fn induced(mut self) -> Self {
+        self.induced = true;
+        self
+    }
+
+    ///
+    /// Stop after this many matches
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    ///
+    /// Only map a pattern node onto a target node when `node_match` accepts their values
+    pub fn node_match<F>(self, node_match: F) -> SubgraphMatcher<'a, 'b, P, G, F, EM>
+        where F: FnMut(&P::Value, &G::Value) -> bool {
+        SubgraphMatcher {
+            pattern: self.pattern,
+            target: self.target,
+            induced: self.induced,
+            limit: self.limit,
+            node_match,
+            edge_match: self.edge_match,
+        }
+    }
+
+    ///
+    /// Only map a pattern edge onto a target edge when `edge_match` accepts their weights
+    pub fn edge_match<F>(self, edge_match: F) -> SubgraphMatcher<'a, 'b, P, G, NM, F>
+        where F: FnMut(&P::Weight, &G::Weight) -> bool {
+        SubgraphMatcher {
+            pattern: self.pattern,
+            target: self.target,
+            induced: self.induced,
+            limit: self.limit,
+            node_match: self.node_match,
+            edge_match,
+        }
+    }
+}
+
+impl<'a, 'b, P, G, NM, EM> IntoIterator for SubgraphMatcher<'a, 'b, P, G, NM, EM>
+    where P: Graph,
+          G: Graph,
+          P::ID: Hash,
+          G::ID: Hash,
+          NM: FnMut(&P::Value, &G::Value) -> bool,
+          EM: FnMut(&P::Weight, &G::Weight) -> bool {
+    type Item = Vec<(&'a P::ID, &'b G::ID)>;
+    type IntoIter = SubgraphMatches<'a, 'b, P, G, NM, EM>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mode = if self.induced { Mode::Induced } else { Mode::Monomorphism };
+        SubgraphMatches {
+            search: Vf2::new(self.pattern, self.target, mode, self.node_match, self.edge_match),
+            remaining: self.limit,
+        }
+    }
+}
+
+///
+/// Iterates over the matches of a [`SubgraphMatcher`], each as `(pattern ID, target ID)` pairs
+pub struct SubgraphMatches<'a, 'b, P: Graph, G: Graph, NM, EM> {
+    search: Vf2<'a, 'b, P, G, NM, EM>,
+    remaining: Option<usize>,
+}
+
+impl<'a, 'b, P, G, NM, EM> Iterator for SubgraphMatches<'a, 'b, P, G, NM, EM>
+    where P: Graph,
+          G: Graph,
+          P::ID: Hash,
+          G::ID: Hash,
+          NM: FnMut(&P::Value, &G::Value) -> bool,
+          EM: FnMut(&P::Weight, &G::Weight) -> bool {
+    type Item = Vec<(&'a P::ID, &'b G::ID)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.remaining {
+            Some(0) => None,
+            Some(remaining) => {
+                *remaining -= 1;
+                self.search.next_mapping()
+            },
+            None => self.search.next_mapping(),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::algorithms::SubgraphMatcher;
+    use crate::{HashGraph, Graph};
+    use std::collections::HashSet;
+
+    fn triangle_with_tail() -> HashGraph<usize, u32, char> {
+        let mut g = HashGraph::new();
+        for (id, value) in [(0, 'a'), (1, 'a'), (2, 'b'), (3, 'b')] {
+            g.add_node_with(id, value).unwrap();
+        }
+        g.add_edge_with(&0, &1, 10).unwrap();
+        g.add_edge_with(&1, &2, 20).unwrap();
+        g.add_edge_with(&2, &0, 30).unwrap();
+        g.add_edge_with(&2, &3, 40).unwrap();
+        g
+    }
+
+    fn path(length: usize) -> HashGraph<usize, (), ()> {
+        let mut g = HashGraph::new();
+        g.add_nodes(0..length + 1).unwrap();
+        for i in 0..length {
+            g.add_edge(&i, &(i + 1)).unwrap();
+        }
+        g
+    }
+
+    fn sorted(mapping: Vec<(&usize, &usize)>) -> Vec<(usize, usize)> {
+        let mut mapping: Vec<(usize, usize)> = mapping.into_iter().map(|(p, t)| (*p, *t)).collect();
+        mapping.sort();
+        mapping
+    }
+
+    #[test]
+    fn monomorphisms() {
+        let target = triangle_with_tail();
+        let matches: HashSet<Vec<(usize, usize)>> = SubgraphMatcher::new(&path(2), &target)
+            .into_iter()
+            .map(sorted)
+            .collect();
+        let expected: HashSet<Vec<(usize, usize)>> = vec![
+            vec![(0, 0), (1, 1), (2, 2)],
+            vec![(0, 1), (1, 2), (2, 0)],
+            vec![(0, 2), (1, 0), (2, 1)],
+            vec![(0, 1), (1, 2), (2, 3)],
+        ].into_iter().collect();
+        assert_eq!(matches, expected);
+    }
+
+    #[test]
+    fn induced_and_limit() {
+        let target = triangle_with_tail();
+        let induced: Vec<_> = SubgraphMatcher::new(&path(2), &target)
+            .induced()
+            .into_iter()
+            .map(sorted)
+            .collect();
+        assert_eq!(induced, vec![vec![(0, 1), (1, 2), (2, 3)]], "The triangle paths have a closing edge");
+
+        assert_eq!(SubgraphMatcher::new(&path(1), &target).limit(2).into_iter().count(), 2);
+        assert_eq!(SubgraphMatcher::new(&path(4), &target).into_iter().next(), None);
+    }
+
+    #[test]
+    fn predicates() {
+        let target = triangle_with_tail();
+        let mut pattern: HashGraph<&str, u32, char> = HashGraph::new();
+        pattern.add_node_with("x", 'b').unwrap();
+        pattern.add_node_with("y", 'b').unwrap();
+        pattern.add_edge_with(&"x", &"y", 0).unwrap();
+
+        let matches: Vec<_> = SubgraphMatcher::new(&pattern, &target)
+            .node_match(|p, t| p == t)
+            .into_iter()
+            .collect();
+        assert_eq!(matches.len(), 1);
+        assert!(matches[0].contains(&(&"x", &2)));
+
+        let heavy = SubgraphMatcher::new(&pattern, &target)
+            .edge_match(|_, t| *t > 25)
+            .into_iter()
+            .count();
+        assert_eq!(heavy, 2);
+    }
+
+    #[test]
+    fn small_pattern_in_large_graph() {
+        let target = path(10_000);
+        let mut checked = 0;
+        let first = SubgraphMatcher::new(&path(2), &target)
+            .node_match(|_, _| {
+                checked += 1;
+                true
+            })
+            .limit(1)
+            .into_iter()
+            .count();
+        assert_eq!(first, 1);
+        assert!(checked < 10, "Only neighbours of mapped nodes should be tried, but {} nodes were", checked);
+    }
+}
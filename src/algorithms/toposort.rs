@@ -0,0 +1,194 @@
+use crate::Graph;
+use crate::algorithms::IndexedGraph;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::fmt::{Debug, Display, Formatter, Result};
+use std::hash::Hash;
+
+///
+/// The reason a graph has no topological order: a cycle, listed so that each ID has an edge to
+/// the next and the last has an edge back to the first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<ID> {
+    cycle: Vec<ID>,
+}
+
+impl<ID> CycleError<ID> {
+
+    pub fn cycle(&self) -> &[ID] {
+        &self.cycle
+    }
+
+    pub fn into_cycle(self) -> Vec<ID> {
+        self.cycle
+    }
+}
+
+impl<ID: Debug> Display for CycleError<ID> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "The graph has a cycle: ")?;
+        for id in &self.cycle {
+            write!(f, "{:?} -> ", id)?;
+        }
+        write!(f, "{:?}", self.cycle[0])
+    }
+}
+
+impl<ID: Debug> std::error::Error for CycleError<ID> {}
+
+///
+/// Holds the nodes that have no unvisited predecessors left, and decides which comes out next
+trait Ready {
+    fn push(&mut self, node: usize);
+    fn pop(&mut self) -> Option<usize>;
+}
+
+impl Ready for VecDeque<usize> {
+    fn push(&mut self, node: usize) {
+        self.push_back(node);
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        self.pop_front()
+    }
+}
+
+impl Ready for BinaryHeap<Reverse<usize>> {
+    fn push(&mut self, node: usize) {
+        BinaryHeap::push(self, Reverse(node));
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        BinaryHeap::pop(self).map(|Reverse(node)| node)
+    }
+}
+
+///
+/// Kahn's algorithm. When there is no order, gives back a cycle among the nodes it couldn't place
+fn kahn<G: Graph, R: Ready>(nodes: &IndexedGraph<'_, G>, mut ready: R) -> std::result::Result<Vec<usize>, Vec<usize>> {
+    let mut remaining: Vec<usize> = nodes.predecessors.iter().map(|predecessors| predecessors.len()).collect();
+    for (node, &count) in remaining.iter().enumerate() {
+        if count == 0 {
+            ready.push(node);
+        }
+    }
+
+    let mut order = Vec::with_capacity(nodes.len());
+    while let Some(node) = ready.pop() {
+        order.push(node);
+        for &next in &nodes.successors[node] {
+            remaining[next] -= 1;
+            if remaining[next] == 0 {
+                ready.push(next);
+            }
+        }
+    }
+
+    if order.len() == nodes.len() {
+        return Ok(order);
+    }
+
+    // Every node left over still has a predecessor that's left over, so walking backwards through
+    // them has to come back around to a node it has already seen
+    let mut seen = vec![None; nodes.len()];
+    let mut walk = vec![];
+    let mut node = (0..nodes.len()).find(|&node| remaining[node] > 0).expect("Some node was left over");
+    while seen[node].is_none() {
+        seen[node] = Some(walk.len());
+        walk.push(node);
+        node = *nodes.predecessors[node].iter()
+            .find(|&&previous| remaining[previous] > 0)
+            .expect("Left over nodes have a left over predecessor");
+    }
+    let mut cycle = walk.split_off(seen[node].expect("The walk came back to this node"));
+    cycle.reverse();
+    Err(cycle)
+}
+
+fn to_ids<'a, G: Graph>(nodes: &IndexedGraph<'a, G>, order: Vec<usize>) -> Vec<&'a G::ID> {
+    order.into_iter().map(|node| nodes.id(node)).collect()
+}
+
+///
+/// Orders the nodes so that every edge goes from an earlier node to a later one, using Kahn's
+/// algorithm. Nodes that are ready at the same time come out in the order of their IDs, so the
+/// result doesn't depend on how the graph stores its nodes
+pub fn topological_sort<G>(graph: &G) -> std::result::Result<Vec<&G::ID>, CycleError<&G::ID>>
+    where G: Graph,
+          G::ID: Hash + Ord {
+    let nodes = IndexedGraph::sorted(graph);
+    match kahn(&nodes, VecDeque::new()) {
+        Ok(order) => Ok(to_ids(&nodes, order)),
+        Err(cycle) => Err(CycleError { cycle: to_ids(&nodes, cycle) }),
+    }
+}
+
+///
+/// Finds the topological order that is smallest when compared ID by ID, by always taking the
+/// smallest node that is ready
+pub fn lexicographic_topological_sort<G>(graph: &G) -> std::result::Result<Vec<&G::ID>, CycleError<&G::ID>>
+    where G: Graph,
+          G::ID: Hash + Ord {
+    let nodes = IndexedGraph::sorted(graph);
+    match kahn(&nodes, BinaryHeap::new()) {
+        Ok(order) => Ok(to_ids(&nodes, order)),
+        Err(cycle) => Err(CycleError { cycle: to_ids(&nodes, cycle) }),
+    }
+}
+
+///
+/// Finds a cycle in the graph, if it has one
+pub fn find_cycle<G>(graph: &G) -> Option<Vec<&G::ID>>
+    where G: Graph,
+          G::ID: Hash {
+    let nodes = IndexedGraph::new(graph);
+    kahn(&nodes, VecDeque::new()).err().map(|cycle| to_ids(&nodes, cycle))
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::algorithms::{topological_sort, lexicographic_topological_sort, find_cycle};
+    use crate::directed::Directed;
+    use crate::HashGraph;
+
+    fn build_steps() -> Directed<u32, (), (), HashGraph<u32>> {
+        let mut g = Directed::from(HashGraph::new());
+        g.add_nodes(1..=6).unwrap();
+        for (u, v) in [(5, 2), (6, 2), (2, 1), (4, 1), (6, 3)] {
+            g.add_edge(&u, &v).unwrap();
+        }
+        g
+    }
+
+    #[test]
+    fn deterministic_orders() {
+        let g = build_steps();
+        let order: Vec<u32> = g.topological_sort().unwrap().into_iter().copied().collect();
+        assert_eq!(order, vec![4, 5, 6, 2, 3, 1]);
+        assert_eq!(topological_sort(&*g).unwrap(), g.topological_sort().unwrap());
+
+        let smallest: Vec<u32> = lexicographic_topological_sort(&*g).unwrap().into_iter().copied().collect();
+        assert_eq!(smallest, vec![4, 5, 6, 2, 1, 3]);
+        assert!(g.find_cycle().is_none());
+    }
+
+    #[test]
+    fn cycles_are_reported() {
+        let mut g = build_steps();
+        g.add_edge(&1, &6).unwrap();
+
+        let error = g.topological_sort().unwrap_err();
+        let cycle: Vec<u32> = error.cycle().iter().map(|id| **id).collect();
+        assert_eq!(cycle.len(), 3);
+        for (i, u) in cycle.iter().enumerate() {
+            assert!(g.contains_edge(u, &cycle[(i + 1) % cycle.len()]));
+        }
+        assert!(error.to_string().starts_with("The graph has a cycle: "));
+
+        let mut looped: HashGraph<u32> = HashGraph::new();
+        looped.add_node(0).unwrap();
+        looped.add_edge(&0, &0).unwrap();
+        assert_eq!(find_cycle(&looped), Some(vec![&0]));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::Graph;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+///
+/// A breadth first walk that yields each node it reaches once, looking at edges only as it goes.
+///
+/// The walk remembers which nodes it has reached, so after it runs out it can be moved to a new root
+/// with [`Bfs::move_to`] to reach the rest of the graph without repeating nodes
+pub struct Bfs<'a, G: Graph> {
+    graph: &'a G,
+    queue: VecDeque<&'a G::ID>,
+    discovered: HashSet<&'a G::ID>,
+}
+
+impl<'a, G> Bfs<'a, G>
+    where G: Graph,
+          G::ID: Hash {
+
+    ///
+    /// Starts a walk from `root`, which yields nothing if `root` isn't in the graph
+    pub fn new(graph: &'a G, root: &G::ID) -> Self {
+        let mut bfs = Bfs {
+            graph,
+            queue: VecDeque::new(),
+            discovered: HashSet::new(),
+        };
+        bfs.move_to(root);
+        bfs
+    }
+
+    ///
+    /// Carries on the walk from `root` before whatever was left to visit, still skipping nodes
+    /// that were already reached
+    pub fn move_to(&mut self, root: &G::ID) {
+        if let Some(root) = self.graph.get_node(root).map(|node| node.get_id()) {
+            self.queue.push_front(root);
+        }
+    }
+
+    ///
+    /// Forgets every node the walk has reached and restarts it from `root`
+    pub fn reset(&mut self, root: &G::ID) {
+        self.discovered.clear();
+        self.queue.clear();
+        self.move_to(root);
+    }
+
+    ///
+    /// Whether the walk has already yielded `id`
+    pub fn is_discovered(&self, id: &G::ID) -> bool {
+        self.discovered.contains(id)
+    }
+}
+
+impl<'a, G> Iterator for Bfs<'a, G>
+    where G: Graph,
+          G::ID: Hash {
+    type Item = &'a G::ID;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.queue.pop_front() {
+            if !self.discovered.insert(node) {
+                continue;
+            }
+            let discovered = &self.discovered;
+            self.queue.extend(self.graph.get_adjacent(node).filter(|next| !discovered.contains(*next)));
+            return Some(node);
+        }
+        None
+    }
+}
+
+///
+/// A depth first walk that yields nodes in preorder, looking at edges only as it goes.
+///
+/// Like [`Bfs`], it can be moved to new roots and skips nodes it has already reached
+pub struct Dfs<'a, G: Graph> {
+    graph: &'a G,
+    stack: Vec<&'a G::ID>,
+    discovered: HashSet<&'a G::ID>,
+}
+
+impl<'a, G> Dfs<'a, G>
+    where G: Graph,
+          G::ID: Hash {
+
+    ///
+    /// Starts a walk from `root`, which yields nothing if `root` isn't in the graph
+    pub fn new(graph: &'a G, root: &G::ID) -> Self {
+        let mut dfs = Dfs {
+            graph,
+            stack: vec![],
+            discovered: HashSet::new(),
+        };
+        dfs.move_to(root);
+        dfs
+    }
+
+    ///
+    /// Carries on the walk from `root` before whatever was left to visit, still skipping nodes
+    /// that were already reached
+    pub fn move_to(&mut self, root: &G::ID) {
+        if let Some(root) = self.graph.get_node(root).map(|node| node.get_id()) {
+            self.stack.push(root);
+        }
+    }
+
+    ///
+    /// Forgets every node the walk has reached and restarts it from `root`
+    pub fn reset(&mut self, root: &G::ID) {
+        self.discovered.clear();
+        self.stack.clear();
+        self.move_to(root);
+    }
+
+    ///
+    /// Whether the walk has already yielded `id`
+    pub fn is_discovered(&self, id: &G::ID) -> bool {
+        self.discovered.contains(id)
+    }
+}
+
+impl<'a, G> Iterator for Dfs<'a, G>
+    where G: Graph,
+          G::ID: Hash {
+    type Item = &'a G::ID;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            if !self.discovered.insert(node) {
+                continue;
+            }
+            // Pushed in reverse so that the first adjacent node is visited first
+            let start = self.stack.len();
+            let discovered = &self.discovered;
+            self.stack.extend(self.graph.get_adjacent(node).filter(|next| !discovered.contains(*next)));
+            self.stack[start..].reverse();
+            return Some(node);
+        }
+        None
+    }
+}
+
+///
+/// What a depth first search is doing, as reported to the visitor of [`depth_first_search`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DfsEvent<'a, ID> {
+    ///
+    /// The search reached a node for the first time
+    Discover(&'a ID),
+    ///
+    /// The search reached a new node through the edge `u -> v`
+    TreeEdge(&'a ID, &'a ID),
+    ///
+    /// The edge `u -> v` goes back to a node whose search hasn't finished, so it closes a cycle
+    BackEdge(&'a ID, &'a ID),
+    ///
+    /// The edge `u -> v` goes to a finished descendant of `u`
+    ForwardEdge(&'a ID, &'a ID),
+    ///
+    /// The edge `u -> v` goes to a finished node that isn't a descendant of `u`
+    CrossEdge(&'a ID, &'a ID),
+    ///
+    /// Every node reachable from this one has been searched
+    Finish(&'a ID),
+}
+
+///
+/// Runs a depth first search from each root in turn, skipping roots that were already reached, and
+/// reports every node and edge to `visitor` as it goes.
+///
+/// In an `Undirected` graph each edge is seen from both ends, so the edge back to a node's parent
+/// shows up as a back edge
+pub fn depth_first_search<'a, G, I, F>(graph: &'a G, roots: I, mut visitor: F)
+    where G: Graph,
+          G::ID: Hash + 'a,
+          I: IntoIterator<Item=&'a G::ID>,
+          F: FnMut(DfsEvent<'a, G::ID>) {
+    let mut discovered: HashMap<&G::ID, usize> = HashMap::new();
+    let mut finished: HashSet<&G::ID> = HashSet::new();
+
+    for root in roots {
+        let root = match graph.get_node(root) {
+            Some(node) if !discovered.contains_key(node.get_id()) => node.get_id(),
+            _ => continue,
+        };
+        discovered.insert(root, discovered.len());
+        visitor(DfsEvent::Discover(root));
+        let mut stack = vec![(root, graph.get_adjacent(root))];
+
+        while let Some((node, adjacent)) = stack.last_mut() {
+            let node = *node;
+            match adjacent.next() {
+                Some(next) => match discovered.get(next) {
+                    None => {
+                        discovered.insert(next, discovered.len());
+                        visitor(DfsEvent::TreeEdge(node, next));
+                        visitor(DfsEvent::Discover(next));
+                        stack.push((next, graph.get_adjacent(next)));
+                    },
+                    Some(_) if !finished.contains(next) => visitor(DfsEvent::BackEdge(node, next)),
+                    Some(order) if discovered[node] < *order => visitor(DfsEvent::ForwardEdge(node, next)),
+                    Some(_) => visitor(DfsEvent::CrossEdge(node, next)),
+                },
+                None => {
+                    stack.pop();
+                    finished.insert(node);
+                    visitor(DfsEvent::Finish(node));
+                },
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::algorithms::{Bfs, Dfs, DfsEvent, depth_first_search};
+    use crate::{HashGraph, BTreeGraph};
+
+    fn tree() -> BTreeGraph<u32> {
+        let mut g = BTreeGraph::new();
+        g.add_nodes(0..7).unwrap();
+        for (u, v) in [(0, 1), (0, 2), (1, 3), (1, 4), (2, 5)] {
+            g.add_edge(&u, &v).unwrap();
+        }
+        g
+    }
+
+    #[test]
+    fn walks_and_restarts() {
+        let g = tree();
+        assert_eq!(Bfs::new(&g, &0).copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(Dfs::new(&g, &0).copied().collect::<Vec<_>>(), vec![0, 1, 3, 4, 2, 5]);
+
+        let mut bfs = Bfs::new(&g, &1);
+        assert_eq!(bfs.next(), Some(&1));
+        assert!(bfs.is_discovered(&1));
+        assert!(!bfs.is_discovered(&4));
+        bfs.move_to(&0);
+        assert_eq!(bfs.copied().collect::<Vec<_>>(), vec![0, 3, 4, 2, 5], "Reached nodes are skipped, pending ones are kept");
+
+        let mut dfs = Dfs::new(&g, &1);
+        assert_eq!(dfs.next(), Some(&1));
+        assert!(!dfs.is_discovered(&3));
+        dfs.move_to(&2);
+        assert_eq!(dfs.copied().collect::<Vec<_>>(), vec![2, 5, 3, 4]);
+
+        let mut dfs = Dfs::new(&g, &2);
+        assert_eq!(dfs.by_ref().count(), 2);
+        dfs.move_to(&6);
+        assert_eq!(dfs.next(), Some(&6));
+        dfs.reset(&2);
+        assert_eq!(dfs.count(), 2);
+
+        assert_eq!(Bfs::new(&g, &10).next(), None);
+    }
+
+    #[test]
+    fn reset_drops_pending_nodes() {
+        let mut g: BTreeGraph<u32> = BTreeGraph::new();
+        g.add_nodes(vec![0, 1, 2, 3, 9].into_iter()).unwrap();
+        for (u, v) in [(0, 1), (1, 0), (1, 2), (2, 1), (2, 3), (3, 2)] {
+            g.add_edge(&u, &v).unwrap();
+        }
+
+        let mut bfs = Bfs::new(&g, &0);
+        assert_eq!(bfs.next(), Some(&0));
+        bfs.reset(&9);
+        assert_eq!(bfs.copied().collect::<Vec<_>>(), vec![9]);
+
+        let mut dfs = Dfs::new(&g, &1);
+        assert_eq!(dfs.next(), Some(&1));
+        dfs.reset(&9);
+        assert_eq!(dfs.copied().collect::<Vec<_>>(), vec![9]);
+    }
+
+    #[test]
+    fn dfs_events() {
+        let mut g: BTreeGraph<u32> = BTreeGraph::new();
+        g.add_nodes(0..4).unwrap();
+        for (u, v) in [(0, 1), (1, 2), (2, 0), (0, 2), (3, 1)] {
+            g.add_edge(&u, &v).unwrap();
+        }
+
+        let mut events = vec![];
+        depth_first_search(&g, [&0, &3, &1], |event| events.push(event));
+        assert_eq!(events, vec![
+            DfsEvent::Discover(&0),
+            DfsEvent::TreeEdge(&0, &1),
+            DfsEvent::Discover(&1),
+            DfsEvent::TreeEdge(&1, &2),
+            DfsEvent::Discover(&2),
+            DfsEvent::BackEdge(&2, &0),
+            DfsEvent::Finish(&2),
+            DfsEvent::Finish(&1),
+            DfsEvent::ForwardEdge(&0, &2),
+            DfsEvent::Finish(&0),
+            DfsEvent::Discover(&3),
+            DfsEvent::CrossEdge(&3, &1),
+            DfsEvent::Finish(&3),
+        ]);
+    }
+
+    #[test]
+    fn deep_search() {
+        let mut g: HashGraph<u32> = HashGraph::new();
+        g.add_nodes(0..100_000).unwrap();
+        for i in 0..99_999 {
+            g.add_edge(&i, &(i + 1)).unwrap();
+        }
+        let mut finished = 0;
+        depth_first_search(&g, [&0], |event| if let DfsEvent::Finish(_) = event { finished += 1 });
+        assert_eq!(finished, 100_000);
+        assert_eq!(Dfs::new(&g, &0).count(), 100_000);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{Graph, HashGraph, GraphResult, Node};
+use crate::{Graph, GraphLookup, HashGraph, GraphResult, Node, EdgeId, EdgeRef, algorithms};
+use crate::algorithms::{CycleError, OddCycleError, Bipartition, Dominators, EulerStep, EulerError};
+use crate::GraphError::{EdgeDoesNotExist, EdgeIdDoesNotExist};
+use crate::Operation::{RemoveEdge, UpdateWeight};
 use std::ops::{DerefMut, Deref};
 use std::fmt::{Display, Formatter, Result, Debug};
 use std::hash::Hash;
@@ -37,7 +40,7 @@ impl<ID, W, T, G> DerefMut for Directed<ID, W, T, G> where
 }
 
 pub fn new_hashed_directed<ID, W, T>() -> Directed<ID, W, T, HashGraph<ID, W, T>> where
-    ID : Eq + Hash + Copy {
+    ID : Eq + Hash + Clone {
     Directed(HashGraph::new())
 }
 
@@ -51,6 +54,77 @@ impl<ID, W, T, G> Directed<ID, W, T, G> where G: Graph<ID=ID, Weight=W, Value=T>
 
 }
 
+impl<ID, W, T, G> Directed<ID, W, T, G> where G: Graph<ID=ID, Weight=W, Value=T>,
+                                              ID: Eq + Hash {
+
+    ///
+    /// Groups the nodes into sets that are connected when edge directions are ignored
+    pub fn weakly_connected_components(&self) -> Vec<Vec<&ID>> {
+        algorithms::weakly_connected_components(&self.0)
+    }
+
+    ///
+    /// Groups the nodes into strongly connected components, in topological order
+    pub fn strongly_connected_components(&self) -> Vec<Vec<&ID>> {
+        algorithms::strongly_connected_components(&self.0)
+    }
+
+    ///
+    /// Collapses each strongly connected component into one node whose value lists its members
+    pub fn condensation(&self) -> HashGraph<usize, (), Vec<ID>>
+        where ID: Clone {
+        algorithms::condensation(&self.0)
+    }
+
+    ///
+    /// Orders the nodes so that every edge goes forwards, breaking ties by ID
+    pub fn topological_sort(&self) -> std::result::Result<Vec<&ID>, CycleError<&ID>>
+        where ID: Ord {
+        algorithms::topological_sort(&self.0)
+    }
+
+    ///
+    /// The topological order that is smallest when compared ID by ID
+    pub fn lexicographic_topological_sort(&self) -> std::result::Result<Vec<&ID>, CycleError<&ID>>
+        where ID: Ord {
+        algorithms::lexicographic_topological_sort(&self.0)
+    }
+
+    pub fn find_cycle(&self) -> Option<Vec<&ID>> {
+        algorithms::find_cycle(&self.0)
+    }
+
+    ///
+    /// The dominator tree of the nodes reachable from `root`, or `None` if `root` isn't in the graph
+    pub fn dominators(&self, root: &ID) -> Option<Dominators<'_, ID>> {
+        algorithms::dominators(&self.0, root)
+    }
+
+    ///
+    /// A trail that follows every edge exactly once
+    pub fn eulerian_trail(&self) -> std::result::Result<Vec<EulerStep<'_, ID>>, EulerError<&ID>> {
+        algorithms::eulerian_trail(&self.0)
+    }
+
+    ///
+    /// A trail that follows every edge exactly once and ends where it starts
+    pub fn eulerian_circuit(&self) -> std::result::Result<Vec<EulerStep<'_, ID>>, EulerError<&ID>> {
+        algorithms::eulerian_circuit(&self.0)
+    }
+}
+
+impl<ID, W, T> Directed<ID, W, T, HashGraph<ID, W, T>> where ID: Eq + Hash + Clone {
+
+    ///
+    /// The post-dominator tree of the nodes that can reach `exit`, found as the dominator tree of
+    /// the reversed graph. Returns `None` if `exit` isn't in the graph
+    pub fn post_dominators(&self, exit: &ID) -> Option<Dominators<'_, ID>> {
+        let exit = self.0.get_node(exit)?.get_id();
+        let reverse = self.0.as_reverse();
+        algorithms::dominators(&reverse, &exit).map(Dominators::flatten)
+    }
+}
+
 
 
 
@@ -69,11 +143,11 @@ impl<ID, W, T, G> From<G> for Undirected<ID, W, T, G> where G: Graph<ID=ID, Weig
 }
 
 impl<ID, W, T, G> Undirected<ID, W, T, G> where G: Graph<ID=ID, Weight=W, Value=T>,
-                                                ID: Eq,
+                                                ID: Eq + Clone,
                                                 W: Clone,
                                                 T : Default {
 
-    pub fn add_nodes<I>(&mut self, id: I) -> GraphResult
+    pub fn add_nodes<I>(&mut self, id: I) -> GraphResult<(), ID>
         where
             I: Iterator<Item = ID>,
     {
@@ -85,17 +159,17 @@ impl<ID, W, T, G> Undirected<ID, W, T, G> where G: Graph<ID=ID, Weight=W, Value=
         Ok(())
     }
 
-    pub fn add_node(&mut self, id: ID) -> GraphResult {
+    pub fn add_node(&mut self, id: ID) -> GraphResult<(), ID> {
         self.add_node_with(id, T::default())
     }
 }
 
 
 impl<'a, ID, W, T, G> Undirected<ID, W, T, G> where G: Graph<ID=ID, Weight=W, Value=T>,
-                                                ID: Eq,
+                                                ID: Eq + Clone,
                                                 W: Clone + Default {
 
-    pub fn add_edge(&mut self, u: &'a ID, v: &'a ID) -> GraphResult {
+    pub fn add_edge(&mut self, u: &'a ID, v: &'a ID) -> GraphResult<EdgeId, ID> {
         self.add_edge_with(u, v, Default::default())
     }
 
@@ -128,8 +202,56 @@ impl<ID, W, T, G> Undirected<ID, W, T, G> where G: Graph<ID=ID, Weight=W, Value=
     }
 }
 
+impl<ID, W, T, G> Undirected<ID, W, T, G> where G: Graph<ID=ID, Weight=W, Value=T>,
+                                                W: Clone,
+                                                ID: Eq + Hash + Clone {
+
+    pub fn connected_components(&self) -> Vec<Vec<&ID>> {
+        algorithms::weakly_connected_components(self)
+    }
+
+    ///
+    /// The nodes whose removal would disconnect the rest of their component
+    pub fn articulation_points(&self) -> Vec<&ID> {
+        algorithms::articulation_points(self)
+    }
+
+    ///
+    /// The edges whose removal would disconnect their component
+    pub fn bridges(&self) -> Vec<(&ID, &ID)> {
+        algorithms::bridges(self)
+    }
+
+    pub fn biconnected_components(&self) -> Vec<Vec<&ID>> {
+        algorithms::biconnected_components(self)
+    }
+
+    pub fn two_edge_connected_components(&self) -> Vec<Vec<&ID>> {
+        algorithms::two_edge_connected_components(self)
+    }
+
+    ///
+    /// Splits the nodes into two sides with every edge going between them, or finds an odd cycle
+    /// showing that can't be done
+    pub fn bipartition(&self) -> std::result::Result<Bipartition<'_, ID>, OddCycleError<&ID>> {
+        algorithms::bipartition(self)
+    }
+
+    ///
+    /// A trail that uses every edge exactly once
+    pub fn eulerian_trail(&self) -> std::result::Result<Vec<EulerStep<'_, ID>>, EulerError<&ID>> {
+        algorithms::undirected_eulerian_trail(self)
+    }
+
+    ///
+    /// A trail that uses every edge exactly once and ends where it starts
+    pub fn eulerian_circuit(&self) -> std::result::Result<Vec<EulerStep<'_, ID>>, EulerError<&ID>> {
+        algorithms::undirected_eulerian_circuit(self)
+    }
+}
+
 pub fn new_hashed_undirected<ID, W, T>() -> Undirected<ID, W, T, HashGraph<ID, W, T>> where
-    ID : Eq + Hash + Copy,
+    ID : Eq + Hash + Clone,
     W : Clone {
     Undirected(HashGraph::new())
 }
@@ -138,7 +260,7 @@ pub fn new_hashed_undirected<ID, W, T>() -> Undirected<ID, W, T, HashGraph<ID, W
 impl<ID, W, T, G> Graph for Undirected<ID, W, T, G> where
     G: Graph<ID=ID, Weight=W, Value=T>,
     W: Clone,
-    ID : Eq
+    ID : Eq + Clone
 {
     type ID = ID;
     type Weight = W;
@@ -154,17 +276,85 @@ impl<ID, W, T, G> Graph for Undirected<ID, W, T, G> where
         self.0.get_node_mut(id)
     }
 
-    fn add_node_with(&mut self, id: Self::ID, value: Self::Value) -> GraphResult {
+    fn add_node_with(&mut self, id: Self::ID, value: Self::Value) -> GraphResult<(), Self::ID> {
         self.0.add_node_with(id, value)
     }
 
+    fn remove_node(&mut self, id: &Self::ID) -> GraphResult<Node<Self::ID, Self::Value>, Self::ID> {
+        self.0.remove_node(id)
+    }
+
     fn contains_node(&self, id: &Self::ID) -> bool {
         self.0.contains_node(id)
     }
 
-    fn add_edge_with(&mut self, u: &Self::ID, v: &Self::ID, weight: Self::Weight) -> GraphResult {
-        self.0.add_edge_with(u, v, weight.clone())?;
-        self.0.add_edge_with(v, u, weight)
+    ///
+    /// Adds both `u -> v` and `v -> u` to the backing graph, returning the ID of `u -> v`. A self-loop
+    /// is only added once. If either direction can't be added, the graph is left unchanged
+    fn add_edge_with(&mut self, u: &Self::ID, v: &Self::ID, weight: Self::Weight) -> GraphResult<EdgeId, Self::ID> {
+        if u == v {
+            return self.0.add_edge_with(u, v, weight);
+        }
+        let edge = self.0.add_edge_with(u, v, weight.clone())?;
+        if let Err(error) = self.0.add_edge_with(v, u, weight) {
+            self.0.remove_edge_by_id(edge).unwrap_or_else(|_| unreachable!("The edge was just added"));
+            return Err(error);
+        }
+        Ok(edge)
+    }
+
+    fn remove_edge(&mut self, u: &Self::ID, v: &Self::ID) -> GraphResult<Self::Weight, Self::ID> {
+        for (u, v) in [(u, v), (v, u)] {
+            if !self.0.contains_edge(u, v) {
+                return Err(EdgeDoesNotExist { u: u.clone(), v: v.clone(), operation: RemoveEdge });
+            }
+        }
+        let weight = self.0.remove_edge(u, v)?;
+        if u != v {
+            self.0.remove_edge(v, u)?;
+        }
+        Ok(weight)
+    }
+
+    ///
+    /// Removes the edge with the given ID along with the reverse edge that was added alongside it
+    fn remove_edge_by_id(&mut self, edge: EdgeId) -> GraphResult<Self::Weight, Self::ID> {
+        let reverse = match self.0.get_edge(edge) {
+            None => { return Err(EdgeIdDoesNotExist { edge, operation: RemoveEdge }); },
+            Some(forward) if forward.get_source() == forward.get_target() => None,
+            Some(forward) => {
+                // Parallel edges are always added and removed in pairs, so the n-th `u -> v` edge
+                // was added alongside the n-th `v -> u` edge
+                let (u, v) = (forward.get_source(), forward.get_target());
+                let position = self.0.get_edges(u, v)
+                    .position(|parallel| parallel.get_id() == edge)
+                    .expect("The edge exists");
+                match self.0.get_edges(v, u).nth(position).map(|reverse| reverse.get_id()) {
+                    None => {
+                        return Err(EdgeDoesNotExist { u: v.clone(), v: u.clone(), operation: RemoveEdge });
+                    },
+                    Some(reverse) => Some(reverse),
+                }
+            },
+        };
+
+        let weight = self.0.remove_edge_by_id(edge)?;
+        if let Some(reverse) = reverse {
+            self.0.remove_edge_by_id(reverse)?;
+        }
+        Ok(weight)
+    }
+
+    fn get_edge(&self, edge: EdgeId) -> Option<EdgeRef<'_, Self::ID, Self::Weight>> {
+        self.0.get_edge(edge)
+    }
+
+    fn edge_id(&self, u: &Self::ID, v: &Self::ID) -> Option<EdgeId> {
+        self.0.edge_id(u, v)
+    }
+
+    fn get_edges<'a>(&'a self, u: &Self::ID, v: &Self::ID) -> impl Iterator<Item=EdgeRef<'a, Self::ID, Self::Weight>> + use<'a, ID, W, T, G> {
+        self.0.get_edges(u, v)
     }
 
     fn contains_edge(&self, u: &Self::ID, v: &Self::ID) -> bool {
@@ -175,39 +365,100 @@ impl<ID, W, T, G> Graph for Undirected<ID, W, T, G> where
         self.0.get_weight(u, v)
     }
 
-    fn get_adjacent(&self, node: &Self::ID) -> Vec<&Self::ID> {
+    fn get_weights<'a>(&'a self, u: &Self::ID, v: &Self::ID) -> impl Iterator<Item=&'a Self::Weight> + use<'a, ID, W, T, G> {
+        self.0.get_weights(u, v)
+    }
+
+    fn update_weight<R, F>(&mut self, u: &Self::ID, v: &Self::ID, f: F) -> GraphResult<R, Self::ID>
+        where F: FnOnce(&mut Self::Weight) -> R {
+        if !self.0.contains_edge(v, u) {
+            return Err(EdgeDoesNotExist { u: v.clone(), v: u.clone(), operation: UpdateWeight });
+        }
+        let output = self.0.update_weight(u, v, f)?;
+        if u != v {
+            let weight = self.0.get_weight(u, v).cloned().expect("Edge was just updated");
+            self.0.update_weight(v, u, |reverse| *reverse = weight)?;
+        }
+        Ok(output)
+    }
+
+    fn get_adjacent<'a>(&'a self, node: &Self::ID) -> impl Iterator<Item=&'a Self::ID> + use<'a, ID, W, T, G> {
         self.0.get_adjacent(node)
     }
 
-    fn nodes(&self) -> Vec<&Node<Self::ID, Self::Value>> {
+    fn get_incoming<'a>(&'a self, node: &Self::ID) -> impl Iterator<Item=&'a Self::ID> + use<'a, ID, W, T, G> {
+        self.0.get_adjacent(node)
+    }
+
+    fn in_degree(&self, node: &Self::ID) -> usize {
+        self.0.out_degree(node)
+    }
+
+    fn out_degree(&self, node: &Self::ID) -> usize {
+        self.0.out_degree(node)
+    }
+
+    fn degree(&self, node: &Self::ID) -> usize {
+        self.0.out_degree(node)
+    }
+
+    fn nodes(&self) -> impl Iterator<Item=&Node<Self::ID, Self::Value>> {
         self.0.nodes()
     }
 
-    fn edges(&self) -> Vec<(&Self::ID, &Self::ID, &Self::Weight)> {
+    fn edges(&self) -> impl Iterator<Item=EdgeRef<'_, Self::ID, Self::Weight>> {
         let mut edges = Set::new();
-        self.0.edges().into_iter().filter(|edge| {
-            let (u, v, _) = edge;
-            let alter = (*v, *u);
-            if edges.contains(&alter) {
+        self.0.edges().filter(move |edge| {
+            let (u, v) = (edge.get_source(), edge.get_target());
+            let alter = (v, u);
+            if u != v && edges.contains(&alter) {
                 false
             }  else {
-                edges.insert((*u, *v));
+                edges.insert((u, v));
                 true
             }
-        }).collect()
+        })
     }
 
     fn num_nodes(&self) -> usize {
-        self.0.num_edges()
+        self.0.num_nodes()
     }
 
+    ///
+    /// Counts the self-loops in the backing graph, which are only stored once
     fn num_edges(&self) -> usize {
-        self.0.num_edges() / 2
+        let loops: usize = self.0.nodes()
+            .map(|node| self.0.get_edges(node.get_id(), node.get_id()).count())
+            .sum();
+        (self.0.num_edges() + loops) / 2
     }
 
     fn take_nodes(self) -> Vec<Node<Self::ID, Self::Value>> {
         self.0.take_nodes()
     }
+
+    fn clear(&mut self) {
+        self.0.clear()
+    }
+}
+
+impl<ID, W, T, G, Q> GraphLookup<Q> for Undirected<ID, W, T, G> where
+    G: GraphLookup<Q> + Graph<ID=ID, Weight=W, Value=T>,
+    W: Clone,
+    ID : Eq + Clone,
+    Q : ?Sized
+{
+    fn lookup_node(&self, id: &Q) -> Option<&Node<ID, T>> {
+        self.0.lookup_node(id)
+    }
+
+    fn lookup_node_mut(&mut self, id: &Q) -> Option<&mut Node<ID, T>> {
+        self.0.lookup_node_mut(id)
+    }
+
+    fn lookup_weight(&self, u: &Q, v: &Q) -> Option<&W> {
+        self.0.lookup_weight(u, v)
+    }
 }
 
 
@@ -216,8 +467,8 @@ impl<ID, W, T, G> Graph for Undirected<ID, W, T, G> where
 
 #[cfg(test)]
 mod test {
-    use crate::directed::{Directed, new_hashed_directed, new_hashed_undirected};
-    use crate::Graph;
+    use crate::directed::{Undirected, new_hashed_directed, new_hashed_undirected};
+    use crate::{Graph, HashMultiGraph};
 
     struct Wrapper(u32);
 
@@ -242,4 +493,119 @@ mod test {
         assert_eq!(undirected.num_edges(), 1, "Undirected should only show 1 edge");
         assert_eq!(undirected.0.num_edges(), 2, "Backing graph has double the amount of edges");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn undirected_removal() {
+        let mut undirected = new_hashed_undirected::<usize, usize, ()>();
+        undirected.add_nodes(0..3).unwrap();
+        undirected.add_edge_with(&0, &1, 15).unwrap();
+        undirected.add_edge_with(&1, &2, 20).unwrap();
+        assert_eq!(undirected.edges().count(), 2, "Each edge should only be listed once");
+        assert_eq!(undirected.get_incoming(&1).count(), 2);
+        assert_eq!(undirected.degree(&1), 2, "Symmetric edges should only be counted once");
+        assert_eq!(undirected.in_degree(&0), undirected.out_degree(&0));
+
+        assert_eq!(undirected.remove_edge(&1, &0).unwrap(), 15);
+        assert!(!undirected.contains_edge(&0, &1));
+        assert!(!undirected.contains_edge(&1, &0));
+        assert_eq!(undirected.num_edges(), 1);
+
+        undirected.remove_node(&2).unwrap();
+        assert_eq!(undirected.num_nodes(), 2);
+        assert_eq!(undirected.num_edges(), 0);
+        assert_eq!(undirected.0.num_edges(), 0, "Both directions should be gone");
+    }
+
+    #[test]
+    fn undirected_weights_stay_in_sync() {
+        let mut undirected = new_hashed_undirected::<usize, usize, ()>();
+        undirected.add_nodes(0..2).unwrap();
+        undirected.add_edge_with(&0, &1, 15).unwrap();
+
+        assert_eq!(undirected.set_weight(&1, &0, 20).unwrap(), 15);
+        assert_eq!(undirected.get_weight(&0, &1), Some(&20));
+        assert_eq!(undirected.get_weight(&1, &0), Some(&20));
+
+        undirected.update_weight(&0, &1, |weight| *weight += 5).unwrap();
+        assert_eq!(undirected.0[(0, 1)], 25);
+        assert_eq!(undirected.0[(1, 0)], 25);
+        assert!(undirected.set_weight(&0, &0, 1).is_err());
+    }
+
+    #[test]
+    fn undirected_edge_ids() {
+        let mut transit = Undirected::from(HashMultiGraph::<usize, usize, ()>::new());
+        transit.add_nodes(0..2).unwrap();
+        let slow = transit.add_edge_with(&0, &1, 12).unwrap();
+        let fast = transit.add_edge_with(&0, &1, 4).unwrap();
+
+        assert_eq!(transit.edge_weight(fast), Some(&4));
+        assert_eq!(transit.remove_edge_by_id(fast).unwrap(), 4);
+        assert_eq!(transit.num_edges(), 1);
+        assert_eq!(transit.get_weights(&1, &0).collect::<Vec<_>>(), vec![&12], "The matching reverse edge should be removed");
+        assert!(transit.get_edge(slow).is_some());
+    }
+
+    #[test]
+    fn undirected_self_loops() {
+        let mut simple = new_hashed_undirected::<usize, usize, ()>();
+        simple.add_nodes(0..2).unwrap();
+        simple.add_edge_with(&1, &1, 3).unwrap();
+        assert!(simple.contains_edge(&1, &1));
+        assert_eq!(simple.num_edges(), 1);
+        assert_eq!(simple.edges().count(), 1);
+        assert!(simple.add_edge_with(&1, &1, 3).is_err());
+        assert_eq!(simple.remove_edge(&1, &1).unwrap(), 3);
+        assert_eq!(simple.num_edges(), 0);
+
+        let mut multi = Undirected::from(HashMultiGraph::<usize, usize, ()>::new());
+        multi.add_nodes(0..2).unwrap();
+        multi.add_edge_with(&0, &0, 1).unwrap();
+        multi.add_edge_with(&0, &0, 2).unwrap();
+        multi.add_edge_with(&0, &1, 3).unwrap();
+        assert_eq!(multi.num_edges(), 3);
+        assert_eq!(multi.edges().count(), 3);
+        assert_eq!(multi.remove_edge(&0, &0).unwrap(), 1);
+        assert_eq!(multi.get_weights(&0, &0).collect::<Vec<_>>(), vec![&2]);
+        assert_eq!(multi.num_edges(), 2);
+    }
+
+    #[test]
+    fn undirected_parallel_edges_stay_paired() {
+        let mut g = Undirected::from(HashMultiGraph::<usize, usize, ()>::new());
+        g.add_nodes(0..2).unwrap();
+        g.add_edge_with(&0, &1, 1).unwrap();
+        g.add_edge_with(&1, &0, 2).unwrap();
+        let third = g.add_edge_with(&0, &1, 3).unwrap();
+
+        assert_eq!(g.remove_edge(&1, &0).unwrap(), 1);
+        g.add_edge_with(&0, &1, 4).unwrap();
+        assert_eq!(g.remove_edge_by_id(third).unwrap(), 3);
+        assert_eq!(g.get_weights(&0, &1).collect::<Vec<_>>(), vec![&2, &4]);
+        assert_eq!(g.get_weights(&1, &0).collect::<Vec<_>>(), vec![&2, &4]);
+
+        // Either half of a pair removes the other
+        let reverse = g.get_edges(&1, &0).next().unwrap().get_id();
+        assert_eq!(g.remove_edge_by_id(reverse).unwrap(), 2);
+        assert_eq!(g.get_weights(&0, &1).collect::<Vec<_>>(), vec![&4]);
+        assert_eq!(g.get_weights(&1, &0).collect::<Vec<_>>(), vec![&4]);
+        assert_eq!(g.num_edges(), 1);
+    }
+
+    #[test]
+    fn undirected_changes_are_all_or_nothing() {
+        let mut undirected = new_hashed_undirected::<usize, usize, ()>();
+        undirected.add_nodes(0..3).unwrap();
+        undirected.0.add_edge_with(&1, &0, 7).unwrap();
+
+        assert!(undirected.add_edge_with(&0, &1, 5).is_err());
+        assert!(!undirected.0.contains_edge(&0, &1), "The forward edge should be rolled back");
+        assert!(undirected.add_edge_with(&0, &5, 5).is_err());
+        assert_eq!(undirected.0.num_edges(), 1);
+
+        undirected.0.add_edge_with(&2, &1, 4).unwrap();
+        assert!(undirected.remove_edge(&2, &1).is_err());
+        assert!(undirected.remove_edge(&1, &2).is_err());
+        assert!(undirected.0.contains_edge(&2, &1), "Nothing should be removed when a direction is missing");
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt::{Display, Formatter, Debug, Result};
+
+///
+/// Identifies a single edge within a graph.
+///
+/// Edge IDs are handed out by a graph when an edge is added and are never reused by that graph,
+/// so an ID stays valid through weight updates and can tell parallel edges apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeId(usize);
+
+impl EdgeId {
+    pub(crate) fn new(index: usize) -> Self {
+        EdgeId(index)
+    }
+
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+impl Display for EdgeId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "e{}", self.0)
+    }
+}
+
+///
+/// A borrowed view of an edge in a graph
+pub struct EdgeRef<'a, ID, W> {
+    id: EdgeId,
+    source: &'a ID,
+    target: &'a ID,
+    weight: &'a W,
+}
+
+impl<'a, ID, W> EdgeRef<'a, ID, W> {
+    pub fn new(id: EdgeId, source: &'a ID, target: &'a ID, weight: &'a W) -> Self {
+        EdgeRef { id, source, target, weight }
+    }
+
+    pub fn get_id(&self) -> EdgeId {
+        self.id
+    }
+
+    pub fn get_source(&self) -> &'a ID {
+        self.source
+    }
+
+    pub fn get_target(&self) -> &'a ID {
+        self.target
+    }
+
+    pub fn get_weight(&self) -> &'a W {
+        self.weight
+    }
+
+    pub fn into_tuple(self) -> (&'a ID, &'a ID, &'a W) {
+        (self.source, self.target, self.weight)
+    }
+}
+
+impl<'a, ID, W> Clone for EdgeRef<'a, ID, W> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, ID, W> Copy for EdgeRef<'a, ID, W> {}
+
+impl<'a, ID, W> PartialEq for EdgeRef<'a, ID, W> where
+    ID: PartialEq,
+    W: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.source == other.source
+            && self.target == other.target
+            && self.weight == other.weight
+    }
+}
+
+impl<'a, ID, W> Debug for EdgeRef<'a, ID, W> where
+    ID: Debug,
+    W: Debug {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{{{}: {:?} -> {:?} ({:?})}}", self.id, self.source, self.target, self.weight)
+    }
+}
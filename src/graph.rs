@@ -1,14 +1,19 @@
 use std::hash::Hash;
 use std::collections::HashMap;
-use crate::Node;
-use crate::graph::GraphError::{IdExists, IdDoesNotExist, EdgeAlreadyExists};
+use crate::{Node, EdgeId, EdgeRef};
+use crate::graph::GraphError::IdSpaceExhausted;
 use std::ops::{Index, IndexMut, Add};
 use std::fmt::{Debug, Formatter, Result, Display};
 pub use crate::graph::hash_graph::HashGraph;
+pub use crate::graph::btree_graph::BTreeGraph;
+pub use crate::graph::hash_multi_graph::HashMultiGraph;
+pub use crate::graph::builder::{GraphBuilder, DuplicatePolicy};
 use num_traits::{PrimInt, One, Zero, FromPrimitive};
 
 mod hash_graph;
 mod btree_graph;
+mod hash_multi_graph;
+mod builder;
 
 
 
@@ -34,23 +39,132 @@ Debug + Display {
         }
     }
 
+    ///
+    /// Like [`Graph::get`], but fails with an error naming the missing node. Indexing a graph by ID
+    /// panics instead
+    fn try_get(&self, id: &Self::ID) -> GraphResult<&Self::Value, Self::ID>
+        where Self::ID: Clone {
+        self.get(id).ok_or_else(|| GraphError::IdDoesNotExist { id: id.clone(), operation: Operation::Index })
+    }
+
+    ///
+    /// Like [`Graph::get_mut`], but fails with an error naming the missing node
+    fn try_get_mut(&mut self, id: &Self::ID) -> GraphResult<&mut Self::Value, Self::ID>
+        where Self::ID: Clone {
+        self.get_mut(id).ok_or_else(|| GraphError::IdDoesNotExist { id: id.clone(), operation: Operation::Index })
+    }
+
     fn get_node(&self, id: &Self::ID) -> Option<&Node<Self::ID, Self::Value>>;
     fn get_node_mut(&mut self, id: &Self::ID) -> Option<&mut Node<Self::ID, Self::Value>>;
 
-    fn add_node_with(&mut self, id: Self::ID, value: Self::Value) -> GraphResult;
+    fn add_node_with(&mut self, id: Self::ID, value: Self::Value) -> GraphResult<(), Self::ID>;
 
+    ///
+    /// Removes a node from the graph, along with every edge that starts or ends at it
+    fn remove_node(&mut self, id: &Self::ID) -> GraphResult<Node<Self::ID, Self::Value>, Self::ID>;
 
     fn contains_node(&self, id: &Self::ID) -> bool;
 
 
 
-    fn add_edge_with(&mut self, u: &Self::ID, v: &Self::ID, weight: Self::Weight) -> GraphResult;
+    ///
+    /// Adds the edge `u -> v`, returning the ID the graph gave it
+    fn add_edge_with(&mut self, u: &Self::ID, v: &Self::ID, weight: Self::Weight) -> GraphResult<EdgeId, Self::ID>;
+    ///
+    /// Removes the edge `u -> v` from the graph, returning its weight. In a multigraph this is the
+    /// oldest `u -> v` edge, the same one the other `u`, `v` methods act on
+    fn remove_edge(&mut self, u: &Self::ID, v: &Self::ID) -> GraphResult<Self::Weight, Self::ID>;
+    ///
+    /// Removes the edge with the given ID from the graph, returning its weight
+    fn remove_edge_by_id(&mut self, edge: EdgeId) -> GraphResult<Self::Weight, Self::ID>;
+    ///
+    /// Gets the edge with the given ID
+    fn get_edge(&self, edge: EdgeId) -> Option<EdgeRef<'_, Self::ID, Self::Weight>>;
+    ///
+    /// Gets the ID of the edge `u -> v`. In a multigraph, this is the oldest `u -> v` edge
+    fn edge_id(&self, u: &Self::ID, v: &Self::ID) -> Option<EdgeId>;
+    ///
+    /// Iterates over every `u -> v` edge. Only multigraphs can have more than one
+    fn get_edges<'a>(&'a self, u: &Self::ID, v: &Self::ID) -> impl Iterator<Item=EdgeRef<'a, Self::ID, Self::Weight>> + use<'a, Self> {
+        self.edge_id(u, v).and_then(|edge| self.get_edge(edge)).into_iter()
+    }
+    ///
+    /// Gets the `(u, v)` endpoints of the edge with the given ID
+    fn edge_endpoints(&self, edge: EdgeId) -> Option<(&Self::ID, &Self::ID)> {
+        self.get_edge(edge).map(|edge| (edge.get_source(), edge.get_target()))
+    }
+    ///
+    /// Gets the weight of the edge with the given ID
+    fn edge_weight(&self, edge: EdgeId) -> Option<&Self::Weight> {
+        self.get_edge(edge).map(|edge| edge.get_weight())
+    }
     fn contains_edge(&self, u: &Self::ID, v: &Self::ID) -> bool;
     fn get_weight(&self, u: &Self::ID, v: &Self::ID) -> Option<&Self::Weight>;
-    fn get_adjacent(&self, node: &Self::ID) -> Vec<&Self::ID>;
+    ///
+    /// Like [`Graph::get_weight`], but fails with an error naming the missing edge. Indexing a graph
+    /// by `(u, v)` panics instead
+    fn try_get_weight(&self, u: &Self::ID, v: &Self::ID) -> GraphResult<&Self::Weight, Self::ID>
+        where Self::ID: Clone {
+        self.get_weight(u, v).ok_or_else(|| GraphError::EdgeDoesNotExist { u: u.clone(), v: v.clone(), operation: Operation::Index })
+    }
+    ///
+    /// Iterates over the weights of every `u -> v` edge. Only multigraphs can have more than one
+    fn get_weights<'a>(&'a self, u: &Self::ID, v: &Self::ID) -> impl Iterator<Item=&'a Self::Weight> + use<'a, Self> {
+        self.get_edges(u, v).map(|edge| edge.get_weight())
+    }
+    ///
+    /// Applies `f` to the weight of the edge `u -> v`, returning whatever `f` returns
+    fn update_weight<R, F>(&mut self, u: &Self::ID, v: &Self::ID, f: F) -> GraphResult<R, Self::ID>
+        where F: FnOnce(&mut Self::Weight) -> R;
+    ///
+    /// Replaces the weight of the edge `u -> v`, returning the old weight
+    fn set_weight(&mut self, u: &Self::ID, v: &Self::ID, weight: Self::Weight) -> GraphResult<Self::Weight, Self::ID> {
+        self.update_weight(u, v, |old| std::mem::replace(old, weight))
+    }
+    ///
+    /// Iterates over the IDs of the nodes that `node` has an edge to
+    fn get_adjacent<'a>(&'a self, node: &Self::ID) -> impl Iterator<Item=&'a Self::ID> + use<'a, Self>;
+
+    ///
+    /// Iterates over the IDs of the nodes that have an edge to `node`, i.e. its predecessors
+    fn get_incoming<'a>(&'a self, node: &Self::ID) -> impl Iterator<Item=&'a Self::ID> + use<'a, Self>;
+
+    ///
+    /// The number of edges that end at `node`
+    fn in_degree(&self, node: &Self::ID) -> usize {
+        self.get_incoming(node).count()
+    }
+
+    ///
+    /// The number of edges that start at `node`
+    fn out_degree(&self, node: &Self::ID) -> usize {
+        self.get_adjacent(node).count()
+    }
+
+    ///
+    /// The number of edges that start or end at `node`
+    fn degree(&self, node: &Self::ID) -> usize {
+        self.in_degree(node) + self.out_degree(node)
+    }
+
+    ///
+    /// Iterates over every node in the graph
+    fn nodes(&self) -> impl Iterator<Item=&Node<Self::ID, Self::Value>>;
+    ///
+    /// Iterates over every edge in the graph
+    fn edges(&self) -> impl Iterator<Item=EdgeRef<'_, Self::ID, Self::Weight>>;
+
+    fn get_adjacent_vec(&self, node: &Self::ID) -> Vec<&Self::ID> {
+        self.get_adjacent(node).collect()
+    }
 
-    fn nodes(&self) -> Vec<&Node<Self::ID, Self::Value>>;
-    fn edges(&self) -> Vec<(& Self::ID, &Self::ID, &Self::Weight)>;
+    fn nodes_vec(&self) -> Vec<&Node<Self::ID, Self::Value>> {
+        self.nodes().collect()
+    }
+
+    fn edges_vec(&self) -> Vec<EdgeRef<'_, Self::ID, Self::Weight>> {
+        self.edges().collect()
+    }
 
 
     fn num_nodes(&self) -> usize;
@@ -58,6 +172,40 @@ Debug + Display {
 
     fn take_nodes(self) -> Vec<Node<Self::ID, Self::Value>>;
 
+    ///
+    /// Removes every node and edge from the graph
+    fn clear(&mut self);
+
+}
+
+///
+/// Lookups by a key `Q` other than the graph's own IDs, such as a `&str` for a graph keyed by
+/// `String`, or the external keys of an [`Interned`](crate::interned::Interned) graph.
+///
+/// The backends also have inherent methods named after the [`Graph`] ones that take borrowed IDs,
+/// so this is mostly for wrappers like [`Undirected`](crate::directed::Undirected) and for generic code.
+/// Weights are changed through [`Graph::update_weight`], which lets wrappers keep edges in sync
+pub trait GraphLookup<Q : ?Sized> : Graph {
+
+    fn lookup_node(&self, id: &Q) -> Option<&Node<Self::ID, Self::Value>>;
+    fn lookup_node_mut(&mut self, id: &Q) -> Option<&mut Node<Self::ID, Self::Value>>;
+    fn lookup_weight(&self, u: &Q, v: &Q) -> Option<&Self::Weight>;
+
+    fn lookup(&self, id: &Q) -> Option<&Self::Value> {
+        self.lookup_node(id).map(|node| node.get_value())
+    }
+
+    fn lookup_mut(&mut self, id: &Q) -> Option<&mut Self::Value> {
+        self.lookup_node_mut(id).map(|node| node.get_value_mut())
+    }
+
+    fn has_node(&self, id: &Q) -> bool {
+        self.lookup_node(id).is_some()
+    }
+
+    fn has_edge(&self, u: &Q, v: &Q) -> bool {
+        self.lookup_weight(u, v).is_some()
+    }
 }
 
 pub trait GraphRef<'a, ID : 'a, W : 'a, T : 'a, G>
@@ -78,36 +226,25 @@ pub trait GraphReverse<ID, W, T, G = Self>
 pub trait GraphTools<ID> : Graph<ID=ID> where
     ID : PrimInt + Zero + One + Add + Eq + FromPrimitive
 {
-    fn add_node_auto_id(&mut self, value: Self::Value) -> GraphResult<ID> {
-        if let Some(mut next_id) = ID::from_usize(self.num_nodes()) {
-            while self.get_node(&next_id).is_some() {
-                next_id = next_id + ID::one();
-            }
-
-            self.add_node_with(next_id.clone(), value)?;
-            Ok(next_id)
-        } else {
-            panic!("Can't create anymore nodes")
+    ///
+    /// Adds a node with the lowest unused ID at or above the number of nodes in the graph
+    fn add_node_auto_id(&mut self, value: Self::Value) -> GraphResult<ID, ID> {
+        let mut next_id = ID::from_usize(self.num_nodes()).ok_or(IdSpaceExhausted)?;
+        while self.get_node(&next_id).is_some() {
+            next_id = next_id.checked_add(&ID::one()).ok_or(IdSpaceExhausted)?;
         }
-    }
 
-    fn add_nodes_auto_id(&mut self, values: Vec<Self::Value>) -> GraphResult<Vec<ID>> {
-        if let Some(mut next_id) = ID::from_usize(self.num_nodes()) {
-            let mut output = Vec::with_capacity(values.len());
-            for val in values {
-                while self.get_node(&next_id).is_some() {
-                    next_id = next_id + ID::one();
-                }
-
-                self.add_node_with(next_id.clone(), val)?;
-                output.push(next_id.clone());
-                next_id = next_id + ID::one();
-            }
+        self.add_node_with(next_id, value)?;
+        Ok(next_id)
+    }
 
-            Ok(output)
-        } else {
-            panic!("Can't create anymore nodes")
+    fn add_nodes_auto_id(&mut self, values: Vec<Self::Value>) -> GraphResult<Vec<ID>, ID> {
+        let mut output = Vec::with_capacity(values.len());
+        for val in values {
+            output.push(self.add_node_auto_id(val)?);
         }
+
+        Ok(output)
     }
 
 
@@ -123,12 +260,103 @@ impl <ID, G> GraphTools<ID> for G
 
 
 
-#[derive(Debug)]
-pub enum GraphError {
-    IdExists,
-    IdDoesNotExist,
-    EdgeAlreadyExists,
+///
+/// What the graph was doing when a [`GraphError`] happened
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    AddNode,
+    RemoveNode,
+    AddEdge,
+    RemoveEdge,
+    UpdateWeight,
+    Index,
+    AutoId,
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let description = match self {
+            Operation::AddNode => "adding a node",
+            Operation::RemoveNode => "removing a node",
+            Operation::AddEdge => "adding an edge",
+            Operation::RemoveEdge => "removing an edge",
+            Operation::UpdateWeight => "updating an edge weight",
+            Operation::Index => "indexing into the graph",
+            Operation::AutoId => "picking a node ID",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+///
+/// An error from a graph operation, carrying the IDs involved and the operation that failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError<ID> {
+    IdExists { id: ID, operation: Operation },
+    IdDoesNotExist { id: ID, operation: Operation },
+    EdgeAlreadyExists { u: ID, v: ID, operation: Operation },
+    EdgeDoesNotExist { u: ID, v: ID, operation: Operation },
+    EdgeIdDoesNotExist { edge: EdgeId, operation: Operation },
+    ///
+    /// There are no IDs left to give to a new node
+    IdSpaceExhausted,
+}
+
+impl<ID> GraphError<ID> {
+
+    pub fn operation(&self) -> Operation {
+        match self {
+            GraphError::IdExists { operation, .. } |
+            GraphError::IdDoesNotExist { operation, .. } |
+            GraphError::EdgeAlreadyExists { operation, .. } |
+            GraphError::EdgeDoesNotExist { operation, .. } |
+            GraphError::EdgeIdDoesNotExist { operation, .. } => *operation,
+            GraphError::IdSpaceExhausted => Operation::AutoId,
+        }
+    }
+
+    ///
+    /// Converts the IDs in the error, such as when a wrapper translates its inner graph's IDs
+    pub fn map_ids<NID, F>(self, mut f: F) -> GraphError<NID>
+        where F: FnMut(ID) -> NID {
+        match self {
+            GraphError::IdExists { id, operation } => GraphError::IdExists { id: f(id), operation },
+            GraphError::IdDoesNotExist { id, operation } => GraphError::IdDoesNotExist { id: f(id), operation },
+            GraphError::EdgeAlreadyExists { u, v, operation } => GraphError::EdgeAlreadyExists { u: f(u), v: f(v), operation },
+            GraphError::EdgeDoesNotExist { u, v, operation } => GraphError::EdgeDoesNotExist { u: f(u), v: f(v), operation },
+            GraphError::EdgeIdDoesNotExist { edge, operation } => GraphError::EdgeIdDoesNotExist { edge, operation },
+            GraphError::IdSpaceExhausted => GraphError::IdSpaceExhausted,
+        }
+    }
+}
+
+impl<ID: Debug> Display for GraphError<ID> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Failed {}: ", self.operation())?;
+        match self {
+            GraphError::IdExists { id, .. } => write!(f, "node {:?} already exists", id),
+            GraphError::IdDoesNotExist { id, .. } => write!(f, "node {:?} does not exist", id),
+            GraphError::EdgeAlreadyExists { u, v, .. } => write!(f, "edge {:?} -> {:?} already exists", u, v),
+            GraphError::EdgeDoesNotExist { u, v, .. } => write!(f, "edge {:?} -> {:?} does not exist", u, v),
+            GraphError::EdgeIdDoesNotExist { edge, .. } => write!(f, "edge {} does not exist", edge),
+            GraphError::IdSpaceExhausted => write!(f, "there are no unused IDs left"),
+        }
+    }
 }
 
-pub type GraphResult<T=()> = std::result::Result<T, GraphError>;
+impl<ID: Debug> std::error::Error for GraphError<ID> {}
+
+pub type GraphResult<T, ID> = std::result::Result<T, GraphError<ID>>;
 
+///
+/// Fails with the first of `u` and `v` that isn't in the graph
+pub(crate) fn require_endpoints<G>(graph: &G, u: &G::ID, v: &G::ID, operation: Operation) -> GraphResult<(), G::ID>
+    where G: Graph + ?Sized,
+          G::ID: Clone {
+    for id in [u, v] {
+        if !graph.contains_node(id) {
+            return Err(GraphError::IdDoesNotExist { id: id.clone(), operation });
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,576 @@
+use std::collections::{BTreeMap, BTreeSet};
+use crate::{Node, GraphRef, GraphReverse, EdgeId, EdgeRef};
+use crate::graph::{Graph, GraphLookup, GraphResult, Operation, require_endpoints};
+use crate::graph::GraphError::{IdExists, IdDoesNotExist, EdgeAlreadyExists, EdgeDoesNotExist, EdgeIdDoesNotExist};
+use crate::graph::Operation::{AddNode, RemoveNode, AddEdge, RemoveEdge, UpdateWeight};
+use std::borrow::Borrow;
+use std::ops::{Index, IndexMut, RangeBounds};
+use std::fmt::{Debug, Formatter, Display};
+
+///
+/// A graph backed by ordered maps.
+///
+/// Nodes, edges and adjacent nodes are always visited in ascending `ID` order, which makes
+/// iteration reproducible between runs. `ID` must impl `Ord` and `Clone`
+pub struct BTreeGraph<ID = usize, W = (), T = ()>
+    where
+        ID: Ord + Clone,
+{
+    adjacency: BTreeMap<ID, BTreeMap<ID, (EdgeId, W)>>,
+    incoming: BTreeMap<ID, BTreeSet<ID>>,
+    nodes: BTreeMap<ID, Node<ID, T>>,
+    edges: BTreeMap<EdgeId, (ID, ID)>,
+    next_edge: usize,
+    num_nodes: usize,
+    num_edges: usize,
+}
+
+impl<ID, W, T> Display for BTreeGraph<ID, W, T> where
+    ID: Ord + Clone, {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BTreeGraph{{ size = {} }}", self.num_nodes)
+    }
+}
+
+impl<ID, W, T> Debug for BTreeGraph<ID, W, T> where
+    ID: Ord + Clone, {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<ID, W, T> Default for BTreeGraph<ID, W, T>
+    where ID: Ord + Clone {
+    fn default() -> Self {
+        Self {
+            adjacency: Default::default(),
+            incoming: Default::default(),
+            nodes: Default::default(),
+            edges: Default::default(),
+            next_edge: 0,
+            num_nodes: 0,
+            num_edges: 0
+        }
+    }
+}
+
+impl<ID, W, T> Graph for BTreeGraph<ID, W, T>
+    where
+        ID: Ord + Clone,
+{
+    type ID = ID;
+    type Weight = W;
+    type Value = T;
+
+    fn get_node(&self, id: &ID) -> Option<&Node<ID, T>> {
+        self.nodes.get(id)
+    }
+
+    fn get_node_mut(&mut self, id: &ID) -> Option<&mut Node<ID, T>> {
+        self.nodes.get_mut(id)
+    }
+
+    fn add_node_with(&mut self, id: ID, value: T) -> GraphResult<(), ID> {
+        if self.nodes.contains_key(&id) {
+            return Err(IdExists { id, operation: AddNode });
+        }
+
+        self.nodes.insert(id.clone(), Node::new(id, value));
+        self.num_nodes += 1;
+        Ok(())
+    }
+
+    fn remove_node(&mut self, id: &ID) -> GraphResult<Node<ID, T>, ID> {
+        let node = match self.nodes.remove(id) {
+            None => { return Err(IdDoesNotExist { id: id.clone(), operation: RemoveNode }); },
+            Some(node) => node,
+        };
+        self.num_nodes -= 1;
+
+        if let Some(outgoing) = self.adjacency.remove(id) {
+            for (v, (edge, _)) in outgoing {
+                if let Some(set) = self.incoming.get_mut(&v) {
+                    set.remove(id);
+                }
+                self.edges.remove(&edge);
+                self.num_edges -= 1;
+            }
+        }
+        if let Some(incoming) = self.incoming.remove(id) {
+            for u in incoming {
+                if let Some((edge, _)) = self.adjacency.get_mut(&u).and_then(|map| map.remove(id)) {
+                    self.edges.remove(&edge);
+                    self.num_edges -= 1;
+                }
+            }
+        }
+
+        Ok(node)
+    }
+
+    fn contains_node(&self, id: &ID) -> bool {
+        self.nodes.contains_key(id)
+    }
+
+    fn add_edge_with(&mut self, u: &ID, v: &ID, weight: W) -> GraphResult<EdgeId, ID> {
+        require_endpoints(self, u, v, AddEdge)?;
+        let map = self.adjacency.entry(u.clone()).or_default();
+        if map.contains_key(v) {
+            return Err(EdgeAlreadyExists { u: u.clone(), v: v.clone(), operation: AddEdge });
+        }
+        let edge = EdgeId::new(self.next_edge);
+        self.next_edge += 1;
+        map.insert(v.clone(), (edge, weight));
+        self.incoming.entry(v.clone()).or_default().insert(u.clone());
+        self.edges.insert(edge, (u.clone(), v.clone()));
+        self.num_edges += 1;
+        Ok(edge)
+    }
+
+    fn remove_edge(&mut self, u: &ID, v: &ID) -> GraphResult<W, ID> {
+        match self.adjacency.get_mut(u).and_then(|map| map.remove(v)) {
+            None => Err(EdgeDoesNotExist { u: u.clone(), v: v.clone(), operation: RemoveEdge }),
+            Some((edge, weight)) => {
+                if let Some(set) = self.incoming.get_mut(v) {
+                    set.remove(u);
+                }
+                self.edges.remove(&edge);
+                self.num_edges -= 1;
+                Ok(weight)
+            },
+        }
+    }
+
+    fn remove_edge_by_id(&mut self, edge: EdgeId) -> GraphResult<W, ID> {
+        match self.edges.get(&edge).cloned() {
+            None => Err(EdgeIdDoesNotExist { edge, operation: RemoveEdge }),
+            Some((u, v)) => self.remove_edge(&u, &v),
+        }
+    }
+
+    fn get_edge(&self, edge: EdgeId) -> Option<EdgeRef<'_, ID, W>> {
+        self.edges.get(&edge)
+            .map(|(u, v)| EdgeRef::new(edge, u, v, &self.adjacency[u][v].1))
+    }
+
+    fn edge_id(&self, u: &ID, v: &ID) -> Option<EdgeId> {
+        self.adjacency.get(u)
+            .and_then(|map| map.get(v))
+            .map(|(edge, _)| *edge)
+    }
+
+    fn contains_edge(&self, u: &ID, v: &ID) -> bool {
+        match self.adjacency.get(u) {
+            None => false,
+            Some(map) => map.contains_key(v),
+        }
+    }
+
+    fn get_weight(&self, u: &ID, v: &ID) -> Option<&W> {
+        self.adjacency.get(u)
+            .and_then(|map| map.get(v))
+            .map(|(_, weight)| weight)
+    }
+
+    fn update_weight<R, F>(&mut self, u: &ID, v: &ID, f: F) -> GraphResult<R, ID>
+        where F: FnOnce(&mut W) -> R {
+        match self.get_weight_mut(u, v) {
+            None => Err(EdgeDoesNotExist { u: u.clone(), v: v.clone(), operation: UpdateWeight }),
+            Some(weight) => Ok(f(weight)),
+        }
+    }
+
+    fn get_adjacent<'a>(&'a self, node: &ID) -> impl Iterator<Item=&'a ID> + use<'a, ID, W, T> {
+        self.adjacency.get(node).into_iter().flat_map(|map| map.keys())
+    }
+
+    fn get_incoming<'a>(&'a self, node: &ID) -> impl Iterator<Item=&'a ID> + use<'a, ID, W, T> {
+        self.incoming.get(node).into_iter().flatten()
+    }
+
+    fn in_degree(&self, node: &ID) -> usize {
+        self.incoming.get(node).map_or(0, |set| set.len())
+    }
+
+    fn out_degree(&self, node: &ID) -> usize {
+        self.adjacency.get(node).map_or(0, |map| map.len())
+    }
+
+    fn nodes(&self) -> impl Iterator<Item=&Node<Self::ID, Self::Value>> {
+        self.nodes.values()
+    }
+
+    fn edges(&self) -> impl Iterator<Item=EdgeRef<'_, Self::ID, Self::Weight>> {
+        self.adjacency.iter()
+            .flat_map(|(u, map)| map.iter().map(move |(v, (edge, weight))| EdgeRef::new(*edge, u, v, weight)))
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.num_nodes
+    }
+
+    fn num_edges(&self) -> usize {
+        self.num_edges
+    }
+
+    fn take_nodes(self) -> Vec<Node<Self::ID, Self::Value>> {
+        self.nodes.into_values().collect()
+    }
+
+    fn clear(&mut self) {
+        self.adjacency.clear();
+        self.incoming.clear();
+        self.nodes.clear();
+        self.edges.clear();
+        self.num_nodes = 0;
+        self.num_edges = 0;
+    }
+}
+
+impl<ID, W, T> BTreeGraph<ID, W, T>
+    where
+        ID: Ord + Clone {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Looks up a node by any borrowed form of its ID, such as a `&str` for a graph keyed by `String`
+    pub fn get_node<Q>(&self, id: &Q) -> Option<&Node<ID, T>>
+        where ID: Borrow<Q>, Q: Ord + ?Sized {
+        self.nodes.get(id)
+    }
+
+    pub fn get_node_mut<Q>(&mut self, id: &Q) -> Option<&mut Node<ID, T>>
+        where ID: Borrow<Q>, Q: Ord + ?Sized {
+        self.nodes.get_mut(id)
+    }
+
+    pub fn get<Q>(&self, id: &Q) -> Option<&T>
+        where ID: Borrow<Q>, Q: Ord + ?Sized {
+        self.get_node(id).map(|node| node.get_value())
+    }
+
+    pub fn get_mut<Q>(&mut self, id: &Q) -> Option<&mut T>
+        where ID: Borrow<Q>, Q: Ord + ?Sized {
+        self.get_node_mut(id).map(|node| node.get_value_mut())
+    }
+
+    pub fn contains_node<Q>(&self, id: &Q) -> bool
+        where ID: Borrow<Q>, Q: Ord + ?Sized {
+        self.nodes.contains_key(id)
+    }
+
+    pub fn contains_edge<Q>(&self, u: &Q, v: &Q) -> bool
+        where ID: Borrow<Q>, Q: Ord + ?Sized {
+        self.adjacency.get(u).is_some_and(|map| map.contains_key(v))
+    }
+
+    pub fn get_weight<Q>(&self, u: &Q, v: &Q) -> Option<&W>
+        where ID: Borrow<Q>, Q: Ord + ?Sized {
+        self.adjacency.get(u)
+            .and_then(|map| map.get(v))
+            .map(|(_, weight)| weight)
+    }
+
+    pub fn get_weight_mut<Q>(&mut self, u: &Q, v: &Q) -> Option<&mut W>
+        where ID: Borrow<Q>, Q: Ord + ?Sized {
+        self.adjacency.get_mut(u)
+            .and_then(|map| map.get_mut(v))
+            .map(|(_, weight)| weight)
+    }
+
+    ///
+    /// Gets the nodes whose IDs fall within `range`, in ascending order
+    pub fn range<R>(&self, range: R) -> impl Iterator<Item=&Node<ID, T>>
+        where R: RangeBounds<ID> {
+        self.nodes.range(range).map(|(_, node)| node)
+    }
+
+    ///
+    /// Gets the nodes adjacent to `node` whose IDs fall within `range`, in ascending order
+    pub fn adjacent_range<R>(&self, node: &ID, range: R) -> impl Iterator<Item=(&ID, &W)>
+        where R: RangeBounds<ID> {
+        self.adjacency.get(node)
+            .map(|map| map.range(range))
+            .into_iter()
+            .flatten()
+            .map(|(v, (_, weight))| (v, weight))
+    }
+
+    ///
+    /// Gets the edges whose source IDs fall within `range`, ordered by source then target
+    pub fn edges_range<R>(&self, range: R) -> impl Iterator<Item=EdgeRef<'_, ID, W>>
+        where R: RangeBounds<ID> {
+        self.adjacency.range(range)
+            .flat_map(|(u, map)| map.iter().map(move |(v, (edge, weight))| EdgeRef::new(*edge, u, v, weight)))
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn disassemble(mut self) -> (Vec<Node<ID, T>>, Vec<(ID, ID, W)>) {
+        let adj = std::mem::take(&mut self.adjacency);
+        let edges =
+            adj.into_iter().flat_map(
+                |(id1, map)| {
+                    map.into_iter().map(
+                        move |(id2, (_, weight))| {
+                            (id1.clone(), id2, weight)
+                        }
+                    )
+                }
+            ).collect();
+
+        let nodes = self.take_nodes();
+
+        (nodes, edges)
+    }
+
+    pub fn as_reverse(&self) -> BTreeGraph<&ID, &W, &T> {
+        self.to_reference_graph().into_reverse()
+    }
+}
+
+impl<ID, W, T> BTreeGraph<ID, W, T>
+    where
+        ID: Ord + Clone,
+        T: Copy,
+{
+    pub fn add_nodes_with<I>(&mut self, id: I, value: T) -> GraphResult<(), ID>
+        where
+            I: Iterator<Item = ID>,
+    {
+        for n in id {
+            self.add_node_with(n, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl<ID, W, T> BTreeGraph<ID, W, T>
+    where
+        ID: Ord + Clone,
+        T: Default,
+{
+    pub fn add_nodes<I>(&mut self, id: I) -> GraphResult<(), ID>
+        where
+            I: Iterator<Item = ID>,
+    {
+        for n in id {
+            self.add_node_with(n, T::default())?;
+        }
+        Ok(())
+    }
+
+    pub fn add_node(&mut self, id: ID) -> GraphResult<(), ID> {
+        self.add_node_with(id, T::default())
+    }
+}
+
+impl<ID, W, T> BTreeGraph<ID, W, T>
+    where
+        ID: Ord + Clone,
+        W: Default,
+{
+    ///
+    /// If the `W` of the graph has a default value, allows for the adding of edges without a weight specified
+    pub fn add_edge(&mut self, u: &ID, v: &ID) -> GraphResult<EdgeId, ID> {
+        self.add_edge_with(u, v, Default::default())
+    }
+}
+
+
+impl<ID, W, T, Q> GraphLookup<Q> for BTreeGraph<ID, W, T>
+    where
+        ID: Ord + Clone,
+        ID: Borrow<Q>,
+        Q: Ord + ?Sized,
+{
+    fn lookup_node(&self, id: &Q) -> Option<&Node<ID, T>> {
+        self.get_node(id)
+    }
+
+    fn lookup_node_mut(&mut self, id: &Q) -> Option<&mut Node<ID, T>> {
+        self.get_node_mut(id)
+    }
+
+    fn lookup_weight(&self, u: &Q, v: &Q) -> Option<&W> {
+        self.get_weight(u, v)
+    }
+}
+
+impl<ID, W, T> Clone for BTreeGraph<ID, W, T>
+    where
+        ID: Ord + Clone,
+        W: Clone,
+        T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            adjacency: self.adjacency.clone(),
+            incoming: self.incoming.clone(),
+            nodes: self.nodes.clone(),
+            edges: self.edges.clone(),
+            next_edge: self.next_edge,
+            num_nodes: self.num_nodes,
+            num_edges: self.num_edges,
+        }
+    }
+}
+
+///
+/// Panics if the node doesn't exist. [`Graph::try_get`] gives a [`GraphError`](crate::GraphError) naming the node instead
+impl<ID, W, T> Index<ID> for BTreeGraph<ID, W, T>
+    where
+        ID: Ord + Clone,
+{
+    type Output = T;
+
+    fn index(&self, index: ID) -> &Self::Output {
+        match self.nodes.get(&index) {
+            Some(node) => node.get_value(),
+            None => panic!("Failed {}: node does not exist", Operation::Index),
+        }
+    }
+}
+
+///
+/// Panics if the node doesn't exist. [`Graph::try_get_mut`] gives a [`GraphError`](crate::GraphError) naming the node instead
+impl<ID, W, T> IndexMut<ID> for BTreeGraph<ID, W, T>
+    where
+        ID: Ord + Clone,
+{
+    fn index_mut(&mut self, index: ID) -> &mut Self::Output {
+        match self.nodes.get_mut(&index) {
+            Some(node) => node.get_value_mut(),
+            None => panic!("Failed {}: node does not exist", Operation::Index),
+        }
+    }
+}
+
+///
+/// Panics if the edge doesn't exist. [`Graph::try_get_weight`] gives a [`GraphError`](crate::GraphError) naming the edge instead
+impl<ID, W, T> Index<(ID, ID)> for BTreeGraph<ID, W, T>
+    where
+        ID: Ord + Clone,
+{
+    type Output = W;
+
+    fn index(&self, index: (ID, ID)) -> &Self::Output {
+        match self.get_weight(&index.0, &index.1) {
+            Some(weight) => weight,
+            None => panic!("Failed {}: edge does not exist", Operation::Index),
+        }
+    }
+}
+
+///
+/// Panics if the edge doesn't exist. [`Graph::update_weight`] gives a [`GraphError`](crate::GraphError) naming the edge instead
+impl<ID, W, T> IndexMut<(ID, ID)> for BTreeGraph<ID, W, T>
+    where
+        ID: Ord + Clone,
+{
+    fn index_mut(&mut self, index: (ID, ID)) -> &mut Self::Output {
+        match self.get_weight_mut(&index.0, &index.1) {
+            Some(weight) => weight,
+            None => panic!("Failed {}: edge does not exist", Operation::Index),
+        }
+    }
+}
+
+impl<'a, ID, W, T> GraphRef<'a, ID, W, T, BTreeGraph<&'a ID, &'a W, &'a T>> for BTreeGraph<ID, W, T> where
+    ID: Ord + Clone, {
+    fn to_reference_graph(&'a self) -> BTreeGraph<&'a ID, &'a W, &'a T> {
+        let mut output = BTreeGraph::new();
+        for node in self.nodes() {
+            output.add_node_with(node.get_id(), node.get_value())
+                .unwrap_or_else(|_| unreachable!("IDs are unique in the source graph"));
+        }
+        for (id1, id2, weight) in self.edges().map(EdgeRef::into_tuple) {
+            output.add_edge_with(&id1, &id2, weight)
+                .unwrap_or_else(|_| unreachable!("Edges are unique in the source graph"));
+        }
+
+        output
+    }
+}
+
+impl<ID, W, T> GraphReverse<ID, W, T> for BTreeGraph<ID, W, T> where
+    ID: Ord + Clone, {
+    fn into_reverse(self) -> Self {
+        let mut output = Self::new();
+        let (nodes, edges) = self.disassemble();
+        for node in nodes {
+            let (id, value) = node.into_tuple();
+            output.add_node_with(id, value)
+                .unwrap_or_else(|_| unreachable!("IDs are unique in the source graph"));
+        }
+
+        for (u, v, weight) in edges {
+            output.add_edge_with(&v, &u, weight)
+                .unwrap_or_else(|_| unreachable!("Edges are unique in the source graph"));
+        }
+
+        output
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::{BTreeGraph, Graph, GraphReverse, EdgeRef};
+
+    #[test]
+    fn ordered_iteration() {
+        let mut g: BTreeGraph<usize, usize, ()> = BTreeGraph::new();
+        g.add_nodes(vec![5, 3, 9, 1, 7].into_iter()).unwrap();
+        g.add_edge_with(&5, &9, 1).unwrap();
+        g.add_edge_with(&5, &1, 2).unwrap();
+        g.add_edge_with(&3, &7, 3).unwrap();
+        g.add_edge_with(&5, &3, 4).unwrap();
+
+        let ids: Vec<_> = g.nodes().map(|n| *n.get_id()).collect();
+        assert_eq!(ids, vec![1, 3, 5, 7, 9]);
+        assert_eq!(g.get_adjacent_vec(&5), vec![&1, &3, &9]);
+        let edges: Vec<_> = g.edges().map(EdgeRef::into_tuple).collect();
+        assert_eq!(edges, vec![(&3, &7, &3), (&5, &1, &2), (&5, &3, &4), (&5, &9, &1)]);
+    }
+
+    #[test]
+    fn range_queries() {
+        let mut g: BTreeGraph<usize, usize, ()> = BTreeGraph::new();
+        g.add_nodes(0..10).unwrap();
+        for v in 1..10 {
+            g.add_edge_with(&0, &v, v * 10).unwrap();
+        }
+        g.add_edge_with(&4, &0, 1).unwrap();
+
+        let ids: Vec<_> = g.range(3..6).map(|n| *n.get_id()).collect();
+        assert_eq!(ids, vec![3, 4, 5]);
+        let adjacent: Vec<_> = g.adjacent_range(&0, 7..).collect();
+        assert_eq!(adjacent, vec![(&7, &70), (&8, &80), (&9, &90)]);
+        let edges: Vec<_> = g.edges_range(1..).map(EdgeRef::into_tuple).collect();
+        assert_eq!(edges, vec![(&4, &0, &1)]);
+    }
+
+    #[test]
+    fn removal_and_reverse() {
+        let mut g: BTreeGraph<usize, usize, ()> = BTreeGraph::new();
+        g.add_nodes(0..3).unwrap();
+        g.add_edge_with(&0, &1, 1).unwrap();
+        g.add_edge_with(&1, &2, 2).unwrap();
+        g.add_edge_with(&2, &0, 3).unwrap();
+
+        assert_eq!(g.get_incoming(&0).collect::<Vec<_>>(), vec![&2]);
+
+        g.remove_node(&0).unwrap();
+        assert_eq!(g.num_nodes(), 2);
+        assert_eq!(g.num_edges(), 1);
+        assert_eq!(g.in_degree(&1), 0);
+        assert_eq!(g.out_degree(&2), 0);
+
+        let reverse = g.into_reverse();
+        assert!(reverse.contains_edge(&2, &1));
+        assert_eq!(reverse[(2, 1)], 2);
+    }
+}
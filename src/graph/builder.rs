@@ -0,0 +1,143 @@
+use crate::graph::{Graph, GraphResult, GraphError};
+use crate::graph::GraphError::IdExists;
+use crate::graph::Operation::AddNode;
+
+///
+/// What a [`GraphBuilder`] does with a node or edge that is already in the graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicatePolicy {
+    ///
+    /// Leave it to the graph, which reports duplicates as errors. Multigraphs still accept parallel
+    /// edges under this policy
+    #[default]
+    Error,
+    ///
+    /// Replace the existing value or weight with the new one
+    Overwrite,
+    ///
+    /// Ignore the new entry
+    KeepFirst,
+}
+
+///
+/// Collects nodes and edges and builds a graph out of them, adding every node before any edge
+pub struct GraphBuilder<G: Graph> {
+    nodes: Vec<(G::ID, G::Value)>,
+    edges: Vec<(G::ID, G::ID, G::Weight)>,
+    duplicates: DuplicatePolicy,
+}
+
+impl<G: Graph> Default for GraphBuilder<G> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            edges: vec![],
+            duplicates: DuplicatePolicy::default(),
+        }
+    }
+}
+
+impl<G> GraphBuilder<G>
+    where G: Graph + Default {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn duplicates(mut self, policy: DuplicatePolicy) -> Self {
+        self.duplicates = policy;
+        self
+    }
+
+    pub fn node(mut self, id: G::ID, value: G::Value) -> Self {
+        self.nodes.push((id, value));
+        self
+    }
+
+    pub fn nodes<I>(mut self, nodes: I) -> Self
+        where I: IntoIterator<Item=(G::ID, G::Value)> {
+        self.nodes.extend(nodes);
+        self
+    }
+
+    pub fn edge(mut self, u: G::ID, v: G::ID, weight: G::Weight) -> Self {
+        self.edges.push((u, v, weight));
+        self
+    }
+
+    pub fn edges<I>(mut self, edges: I) -> Self
+        where I: IntoIterator<Item=(G::ID, G::ID, G::Weight)> {
+        self.edges.extend(edges);
+        self
+    }
+
+    ///
+    /// Builds the graph, failing on the first invalid entry
+    pub fn build(self) -> GraphResult<G, G::ID> {
+        let mut first = None;
+        let graph = self.build_with(|error| {
+            first = Some(error);
+            false
+        });
+        match first {
+            None => Ok(graph),
+            Some(error) => Err(error),
+        }
+    }
+
+    ///
+    /// Builds the graph, skipping over invalid entries and reporting all of them
+    pub fn build_all(self) -> Result<G, Vec<GraphError<G::ID>>> {
+        let mut errors = vec![];
+        let graph = self.build_with(|error| {
+            errors.push(error);
+            true
+        });
+        if errors.is_empty() {
+            Ok(graph)
+        } else {
+            Err(errors)
+        }
+    }
+
+    ///
+    /// Adds every entry to a new graph, handing errors to `on_error`, which returns whether to keep going
+    fn build_with<F>(self, mut on_error: F) -> G
+        where F: FnMut(GraphError<G::ID>) -> bool {
+        let mut graph = G::default();
+
+        for (id, value) in self.nodes {
+            let result = match (self.duplicates, graph.get_mut(&id)) {
+                (DuplicatePolicy::Error, Some(_)) => Err(IdExists { id, operation: AddNode }),
+                (DuplicatePolicy::Overwrite, Some(old)) => {
+                    *old = value;
+                    Ok(())
+                },
+                (DuplicatePolicy::KeepFirst, Some(_)) => Ok(()),
+                (_, None) => graph.add_node_with(id, value),
+            };
+            if let Err(error) = result {
+                if !on_error(error) {
+                    return graph;
+                }
+            }
+        }
+
+        for (u, v, weight) in self.edges {
+            let result = match self.duplicates {
+                DuplicatePolicy::Overwrite if graph.contains_edge(&u, &v) => {
+                    graph.set_weight(&u, &v, weight).map(|_| ())
+                },
+                DuplicatePolicy::KeepFirst if graph.contains_edge(&u, &v) => Ok(()),
+                _ => graph.add_edge_with(&u, &v, weight).map(|_| ()),
+            };
+            if let Err(error) = result {
+                if !on_error(error) {
+                    return graph;
+                }
+            }
+        }
+
+        graph
+    }
+}
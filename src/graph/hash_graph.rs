@@ -1,21 +1,27 @@
 use std::hash::Hash;
-use std::collections::{HashMap, HashSet};
-use crate::{Node, GraphRef, GraphReverse, GraphError};
-use crate::graph::{Graph, GraphResult};
-use crate::graph::GraphError::{IdExists, IdDoesNotExist, EdgeAlreadyExists};
+use std::collections::{HashMap, HashSet, BTreeMap};
+use crate::{Node, GraphRef, GraphReverse, EdgeId, EdgeRef};
+use crate::graph::{Graph, GraphLookup, GraphResult, GraphError, Operation, GraphBuilder, require_endpoints};
+use crate::graph::GraphError::{IdExists, IdDoesNotExist, EdgeAlreadyExists, EdgeDoesNotExist, EdgeIdDoesNotExist};
+use crate::graph::Operation::{AddNode, RemoveNode, AddEdge, RemoveEdge, UpdateWeight};
+use std::borrow::Borrow;
 use std::ops::{Index, IndexMut};
 use std::fmt::{Debug, Formatter, Display};
+use std::convert::TryFrom;
 use std::iter::FromIterator;
 use crate::pathing::spatial::Location;
+use crate::algorithms::{is_isomorphic, weakly_connected_components, component_subgraphs};
 
 
 pub struct HashGraph<ID = usize, W = (), T = ()>
     where
-        ID: Eq + Hash + Copy,
+        ID: Eq + Hash + Clone,
 {
-    adjacency: HashMap<ID, HashMap<ID, W>>,
+    adjacency: HashMap<ID, HashMap<ID, (EdgeId, W)>>,
+    incoming: HashMap<ID, HashSet<ID>>,
     nodes: HashMap<ID, Node<ID, T>>,
-    edges: Vec<(ID, ID)>,
+    edges: BTreeMap<EdgeId, (ID, ID)>,
+    next_edge: usize,
     num_nodes: usize,
     num_edges: usize,
 }
@@ -24,26 +30,28 @@ pub struct HashGraph<ID = usize, W = (), T = ()>
 
 
 impl<ID, W, T> Display for HashGraph<ID, W, T> where
-    ID: Eq + Hash + Copy, {
+    ID: Eq + Hash + Clone, {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "HashGraph{{ size = {} }}", self.num_nodes)
     }
 }
 
 impl<ID, W, T> Debug for HashGraph<ID, W, T> where
-    ID: Eq + Hash + Copy, {
+    ID: Eq + Hash + Clone, {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         Display::fmt(self, f)
     }
 }
 
 impl<ID, W, T> Default for HashGraph<ID, W, T>
-    where ID: Eq + Hash + Copy{
+    where ID: Eq + Hash + Clone{
     fn default() -> Self {
         Self {
             adjacency: Default::default(),
+            incoming: Default::default(),
             nodes: Default::default(),
-            edges: vec![],
+            edges: Default::default(),
+            next_edge: 0,
             num_nodes: 0,
             num_edges: 0
         }
@@ -53,7 +61,7 @@ impl<ID, W, T> Default for HashGraph<ID, W, T>
 
 impl<ID, W, T> Graph for HashGraph<ID, W, T>
     where
-        ID: Eq + Hash + Copy,
+        ID: Eq + Hash + Clone,
 {
     type ID = ID;
     type Weight = W;
@@ -72,10 +80,10 @@ impl<ID, W, T> Graph for HashGraph<ID, W, T>
         self.nodes.get_mut(id)
     }
 
-    fn add_node_with(&mut self, id: ID, value: T) -> GraphResult {
+    fn add_node_with(&mut self, id: ID, value: T) -> GraphResult<(), ID> {
         let n = Node::new(id.clone(), value);
         if self.nodes.contains_key(n.get_id()) {
-            return Err(IdExists);
+            return Err(IdExists { id, operation: AddNode });
         }
 
         self.nodes.insert(id, n);
@@ -83,24 +91,83 @@ impl<ID, W, T> Graph for HashGraph<ID, W, T>
         Ok(())
     }
 
+    fn remove_node(&mut self, id: &ID) -> GraphResult<Node<ID, T>, ID> {
+        let node = match self.nodes.remove(id) {
+            None => { return Err(IdDoesNotExist { id: id.clone(), operation: RemoveNode }); },
+            Some(node) => node,
+        };
+        self.num_nodes -= 1;
+
+        if let Some(outgoing) = self.adjacency.remove(id) {
+            for (v, (edge, _)) in outgoing {
+                if let Some(set) = self.incoming.get_mut(&v) {
+                    set.remove(id);
+                }
+                self.edges.remove(&edge);
+                self.num_edges -= 1;
+            }
+        }
+        if let Some(incoming) = self.incoming.remove(id) {
+            for u in incoming {
+                if let Some((edge, _)) = self.adjacency.get_mut(&u).and_then(|map| map.remove(id)) {
+                    self.edges.remove(&edge);
+                    self.num_edges -= 1;
+                }
+            }
+        }
+
+        Ok(node)
+    }
+
     fn contains_node(&self, id: &ID) -> bool {
         self.nodes.contains_key(&id)
     }
 
-    fn add_edge_with<'a>(&mut self, u: &'a ID, v: &'a ID, weight: W) -> GraphResult {
-        if !self.contains_node(u) {
-            return Err(IdDoesNotExist);
-        } else if !self.contains_node(v) {
-            return Err(IdDoesNotExist);
-        }
-        let map = self.adjacency.entry(*u).or_insert(HashMap::new());
-        if map.contains_key(&v) {
-            return Err(EdgeAlreadyExists);
+    fn add_edge_with(&mut self, u: &ID, v: &ID, weight: W) -> GraphResult<EdgeId, ID> {
+        require_endpoints(self, u, v, AddEdge)?;
+        let map = self.adjacency.entry(u.clone()).or_default();
+        if map.contains_key(v) {
+            return Err(EdgeAlreadyExists { u: u.clone(), v: v.clone(), operation: AddEdge });
         }
-        self.edges.push((*u, *v));
+        let edge = EdgeId::new(self.next_edge);
+        self.next_edge += 1;
+        map.insert(v.clone(), (edge, weight));
+        self.incoming.entry(v.clone()).or_default().insert(u.clone());
+        self.edges.insert(edge, (u.clone(), v.clone()));
         self.num_edges += 1;
-        map.insert(*v, weight);
-        Ok(())
+        Ok(edge)
+    }
+
+    fn remove_edge(&mut self, u: &ID, v: &ID) -> GraphResult<W, ID> {
+        let (edge, weight) = match self.adjacency.get_mut(u).and_then(|map| map.remove(v)) {
+            None => { return Err(EdgeDoesNotExist { u: u.clone(), v: v.clone(), operation: RemoveEdge }); },
+            Some(entry) => entry,
+        };
+        if let Some(set) = self.incoming.get_mut(v) {
+            set.remove(u);
+        }
+
+        self.edges.remove(&edge);
+        self.num_edges -= 1;
+        Ok(weight)
+    }
+
+    fn remove_edge_by_id(&mut self, edge: EdgeId) -> GraphResult<W, ID> {
+        match self.edges.get(&edge).cloned() {
+            None => Err(EdgeIdDoesNotExist { edge, operation: RemoveEdge }),
+            Some((u, v)) => self.remove_edge(&u, &v),
+        }
+    }
+
+    fn get_edge(&self, edge: EdgeId) -> Option<EdgeRef<'_, ID, W>> {
+        self.edges.get(&edge)
+            .map(|(u, v)| EdgeRef::new(edge, u, v, &self.adjacency[u][v].1))
+    }
+
+    fn edge_id(&self, u: &ID, v: &ID) -> Option<EdgeId> {
+        self.adjacency.get(u)
+            .and_then(|map| map.get(v))
+            .map(|(edge, _)| *edge)
     }
 
     fn contains_edge(&self, u: &ID, v: &ID) -> bool {
@@ -114,29 +181,45 @@ impl<ID, W, T> Graph for HashGraph<ID, W, T>
     }
 
     fn get_weight(&self, u: &ID, v: &ID) -> Option<&W> {
-        if !self.contains_edge(u, v) {
-            None
-        } else {
-            self.adjacency.get(&u).unwrap().get(&v)
-        }
+        self.adjacency.get(u)
+            .and_then(|map| map.get(v))
+            .map(|(_, weight)| weight)
     }
 
-    fn get_adjacent(&self, node: &ID) -> Vec<&ID> {
-        match self.adjacency.get(&node) {
-            None => Vec::new(),
-            Some(map) => map.keys().collect(),
+    fn update_weight<R, F>(&mut self, u: &ID, v: &ID, f: F) -> GraphResult<R, ID>
+        where F: FnOnce(&mut W) -> R {
+        match self.get_weight_mut(u, v) {
+            None => Err(EdgeDoesNotExist { u: u.clone(), v: v.clone(), operation: UpdateWeight }),
+            Some(weight) => Ok(f(weight)),
         }
     }
 
+    fn get_adjacent<'a>(&'a self, node: &ID) -> impl Iterator<Item=&'a ID> + use<'a, ID, W, T> {
+        self.adjacency.get(node).into_iter().flat_map(|map| map.keys())
+    }
+
+    fn get_incoming<'a>(&'a self, node: &ID) -> impl Iterator<Item=&'a ID> + use<'a, ID, W, T> {
+        self.incoming.get(node).into_iter().flatten()
+    }
+
+    fn in_degree(&self, node: &ID) -> usize {
+        self.incoming.get(node).map_or(0, |set| set.len())
+    }
+
+    fn out_degree(&self, node: &ID) -> usize {
+        self.adjacency.get(node).map_or(0, |map| map.len())
+    }
+
 
 
 
-    fn nodes(&self) -> Vec<&Node<Self::ID, Self::Value>> {
-        Vec::from_iter(self.nodes.values())
+    fn nodes(&self) -> impl Iterator<Item=&Node<Self::ID, Self::Value>> {
+        self.nodes.values()
     }
 
-    fn edges(&self) -> Vec<(&Self::ID, &Self::ID, &Self::Weight)> {
-        Vec::from_iter(self.edges.iter().map(|(id1, id2)| (id1, id2, self.get_weight(id1, id2).unwrap())))
+    fn edges(&self) -> impl Iterator<Item=EdgeRef<'_, Self::ID, Self::Weight>> {
+        self.edges.iter()
+            .map(move |(edge, (id1, id2))| EdgeRef::new(*edge, id1, id2, &self.adjacency[id1][id2].1))
     }
 
 
@@ -154,47 +237,100 @@ impl<ID, W, T> Graph for HashGraph<ID, W, T>
         ).collect()
     }
 
-
-
-
-    /*
-    pub fn get_sub_graphs(&self) -> Vec<HashGraph<ID, &W, &T>> {
-        unimplemented!()
+    fn clear(&mut self) {
+        self.adjacency.clear();
+        self.incoming.clear();
+        self.nodes.clear();
+        self.edges.clear();
+        self.num_nodes = 0;
+        self.num_edges = 0;
     }
 
-     */
-
-
-
 
 }
 
 
 
-impl<ID, W, T> From<(Vec<(ID, T)>, Vec<(ID, ID, W)>)> for HashGraph<ID, W, T> where
-    ID: Eq + Hash + Copy, {
-    fn from(input: (Vec<(ID, T)>, Vec<(ID, ID, W)>)) -> Self {
+///
+/// Builds a graph out of `(nodes, edges)`, failing on the first duplicate ID, duplicate edge or
+/// edge with a missing endpoint. Use [`HashGraph::builder`] to pick how duplicates are handled
+impl<ID, W, T> TryFrom<(Vec<(ID, T)>, Vec<(ID, ID, W)>)> for HashGraph<ID, W, T> where
+    ID: Eq + Hash + Clone, {
+    type Error = GraphError<ID>;
+
+    fn try_from(input: (Vec<(ID, T)>, Vec<(ID, ID, W)>)) -> Result<Self, Self::Error> {
         let (nodes, edges) = input;
-        let mut output = HashGraph::new();
+        HashGraph::builder()
+            .nodes(nodes)
+            .edges(edges)
+            .build()
+    }
+}
 
-        for (id, value) in nodes {
-            output.add_node_with(id, value);
+///
+/// Adds each `(id, value)` node, replacing the value of any node that is already in the graph
+impl<ID, W, T> Extend<(ID, T)> for HashGraph<ID, W, T> where
+    ID: Eq + Hash + Clone, {
+    fn extend<I: IntoIterator<Item=(ID, T)>>(&mut self, iter: I) {
+        for (id, value) in iter {
+            match self.nodes.get_mut(&id) {
+                Some(node) => *node.get_value_mut() = value,
+                None => {
+                    self.add_node_with(id, value).unwrap_or_else(|_| unreachable!("The node isn't in the graph"));
+                },
+            }
         }
+    }
+}
 
-        for (id1, id2, weight) in edges {
-            output.add_edge_with(&id1, &id2, weight);
+///
+/// Adds each `(u, v, weight)` edge, creating any missing endpoint with a default value and
+/// replacing the weight of any edge that is already in the graph
+impl<ID, W, T> Extend<(ID, ID, W)> for HashGraph<ID, W, T> where
+    ID: Eq + Hash + Clone,
+    T: Default, {
+    fn extend<I: IntoIterator<Item=(ID, ID, W)>>(&mut self, iter: I) {
+        for (u, v, weight) in iter {
+            for id in [&u, &v] {
+                if !self.contains_node(id) {
+                    self.add_node(id.clone()).unwrap_or_else(|_| unreachable!("The node isn't in the graph"));
+                }
+            }
+            match self.get_weight_mut(&u, &v) {
+                Some(old) => *old = weight,
+                None => {
+                    self.add_edge_with(&u, &v, weight).unwrap_or_else(|_| unreachable!("Both endpoints exist"));
+                },
+            }
         }
+    }
+}
+
+impl<ID, W, T> FromIterator<(ID, T)> for HashGraph<ID, W, T> where
+    ID: Eq + Hash + Clone, {
+    fn from_iter<I: IntoIterator<Item=(ID, T)>>(iter: I) -> Self {
+        let mut output = HashGraph::new();
+        output.extend(iter);
+        output
+    }
+}
 
+impl<ID, W, T> FromIterator<(ID, ID, W)> for HashGraph<ID, W, T> where
+    ID: Eq + Hash + Clone,
+    T: Default, {
+    fn from_iter<I: IntoIterator<Item=(ID, ID, W)>>(iter: I) -> Self {
+        let mut output = HashGraph::new();
+        output.extend(iter);
         output
     }
 }
 
 impl<ID, W, T> HashGraph<ID, W, T>
     where
-        ID: Eq + Hash + Copy,
+        ID: Eq + Hash + Clone,
         T: Copy,
 {
-    pub fn add_nodes_with<I>(&mut self, id: I, value: T) -> GraphResult
+    pub fn add_nodes_with<I>(&mut self, id: I, value: T) -> GraphResult<(), ID>
         where
             I: Iterator<Item = ID>,
     {
@@ -209,10 +345,10 @@ impl<ID, W, T> HashGraph<ID, W, T>
 
 impl<ID, W, T> HashGraph<ID, W, T>
     where
-        ID: Eq + Hash + Copy,
+        ID: Eq + Hash + Clone,
         T: Default,
 {
-    pub fn add_nodes<I>(&mut self, id: I) -> GraphResult
+    pub fn add_nodes<I>(&mut self, id: I) -> GraphResult<(), ID>
         where
             I: Iterator<Item = ID>,
     {
@@ -224,22 +360,44 @@ impl<ID, W, T> HashGraph<ID, W, T>
         Ok(())
     }
 
-    pub fn add_node(&mut self, id: ID) -> GraphResult {
+    pub fn add_node(&mut self, id: ID) -> GraphResult<(), ID> {
         self.add_node_with(id, T::default())
     }
 }
 
+
+impl<ID, W, T, Q> GraphLookup<Q> for HashGraph<ID, W, T>
+    where
+        ID: Eq + Hash + Clone,
+        ID: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+{
+    fn lookup_node(&self, id: &Q) -> Option<&Node<ID, T>> {
+        self.get_node(id)
+    }
+
+    fn lookup_node_mut(&mut self, id: &Q) -> Option<&mut Node<ID, T>> {
+        self.get_node_mut(id)
+    }
+
+    fn lookup_weight(&self, u: &Q, v: &Q) -> Option<&W> {
+        self.get_weight(u, v)
+    }
+}
+
 impl<ID, W, T> Clone for HashGraph<ID, W, T>
     where
-        ID: Eq + Hash + Copy,
+        ID: Eq + Hash + Clone,
         W: Clone,
         T: Clone,
 {
     fn clone(&self) -> Self {
         Self {
             adjacency: self.adjacency.clone(),
+            incoming: self.incoming.clone(),
             nodes: self.nodes.clone(),
             edges: self.edges.clone(),
+            next_edge: self.next_edge,
             num_nodes: self.num_nodes,
             num_edges: self.num_edges,
         }
@@ -248,15 +406,15 @@ impl<ID, W, T> Clone for HashGraph<ID, W, T>
 
 impl<ID, W, T> HashGraph<ID, W, T>
     where
-        ID: Eq + Hash + Copy {
+        ID: Eq + Hash + Clone {
     pub fn disassemble(mut self) -> (Vec<Node<ID, T>>, Vec<(ID, ID, W)>){
         let adj = std::mem::replace(&mut self.adjacency, HashMap::new());
         let edges =
             adj.into_iter().map(
                 |(id1, map)| {
                     map.into_iter().map(
-                        move |(id2, weight)| {
-                            (id1, id2, weight)
+                        move |(id2, (_, weight))| {
+                            (id1.clone(), id2, weight)
                         }
                     )
                 }
@@ -271,14 +429,97 @@ impl<ID, W, T> HashGraph<ID, W, T>
         self.to_reference_graph().into_reverse()
     }
 
+    ///
+    /// Splits the graph into one graph per weakly connected component, borrowing from this graph
+    pub fn get_sub_graphs(&self) -> Vec<HashGraph<&ID, &W, &T>> {
+        component_subgraphs(self)
+    }
+
+    ///
+    /// Splits the graph into one graph per weakly connected component. Edges get new IDs in
+    /// their component's graph
+    pub fn into_sub_graphs(self) -> Vec<HashGraph<ID, W, T>> {
+        let components = weakly_connected_components(&self);
+        let mut subgraphs: Vec<HashGraph<ID, W, T>> = components.iter().map(|_| HashGraph::new()).collect();
+        let labels: HashMap<ID, usize> = components.into_iter()
+            .enumerate()
+            .flat_map(|(label, component)| component.into_iter().map(move |id| (id.clone(), label)))
+            .collect();
+
+        let (nodes, edges) = self.disassemble();
+        for node in nodes {
+            let (id, value) = node.into_tuple();
+            subgraphs[labels[&id]].add_node_with(id, value)
+                .unwrap_or_else(|_| unreachable!("IDs are unique in the source graph"));
+        }
+        for (u, v, weight) in edges {
+            subgraphs[labels[&u]].add_edge_with(&u, &v, weight)
+                .unwrap_or_else(|_| unreachable!("Both endpoints are in the same component"));
+        }
+
+        subgraphs
+    }
+
     pub fn new() -> Self {
         Self::default()
     }
+
+    ///
+    /// Starts building a graph, which allows picking how duplicate nodes and edges are handled
+    pub fn builder() -> GraphBuilder<Self> {
+        GraphBuilder::new()
+    }
+
+    ///
+    /// Looks up a node by any borrowed form of its ID, such as a `&str` for a graph keyed by `String`
+    pub fn get_node<Q>(&self, id: &Q) -> Option<&Node<ID, T>>
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.nodes.get(id)
+    }
+
+    pub fn get_node_mut<Q>(&mut self, id: &Q) -> Option<&mut Node<ID, T>>
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.nodes.get_mut(id)
+    }
+
+    pub fn get<Q>(&self, id: &Q) -> Option<&T>
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.get_node(id).map(|node| node.get_value())
+    }
+
+    pub fn get_mut<Q>(&mut self, id: &Q) -> Option<&mut T>
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.get_node_mut(id).map(|node| node.get_value_mut())
+    }
+
+    pub fn contains_node<Q>(&self, id: &Q) -> bool
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.nodes.contains_key(id)
+    }
+
+    pub fn contains_edge<Q>(&self, u: &Q, v: &Q) -> bool
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.adjacency.get(u).is_some_and(|map| map.contains_key(v))
+    }
+
+    pub fn get_weight<Q>(&self, u: &Q, v: &Q) -> Option<&W>
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.adjacency.get(u)
+            .and_then(|map| map.get(v))
+            .map(|(_, weight)| weight)
+    }
+
+    pub fn get_weight_mut<Q>(&mut self, u: &Q, v: &Q) -> Option<&mut W>
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.adjacency.get_mut(u)
+            .and_then(|map| map.get_mut(v))
+            .map(|(_, weight)| weight)
+    }
 }
 
 impl<ID, W, T> HashGraph<ID, W, Option<T>>
     where
-        ID: Eq + Hash + Copy
+        ID: Eq + Hash + Clone
 {
 
 
@@ -286,8 +527,8 @@ impl<ID, W, T> HashGraph<ID, W, Option<T>>
         let mut output = HashGraph::new();
         let (nodes, edges) = self.disassemble();
         for node in nodes {
-            let id = *node.get_id();
-            match node.value {
+            let (id, value) = node.into_tuple();
+            match value {
                 None => { return None; },
                 Some(val) => {
                     output.add_node_with(id, val);
@@ -306,33 +547,28 @@ impl<ID, W, T> HashGraph<ID, W, Option<T>>
 
 impl<'a, ID, W, T> HashGraph<ID, W, T>
     where
-        ID: Eq + Hash + Copy,
+        ID: Eq + Hash + Clone,
         W: Default,
 {
     ///
     /// If the `W` of the graph has a default value, allows for the adding of edges without a weight specified
-    pub fn add_edge(&mut self, u: &'a ID, v: &'a ID) -> GraphResult {
+    pub fn add_edge(&mut self, u: &'a ID, v: &'a ID) -> GraphResult<EdgeId, ID> {
         self.add_edge_with(u, v, Default::default())
     }
 }
 
 impl<'a, ID, W, T> HashGraph<ID, W, T>
     where
-        ID: Eq + Hash + Copy,
+        ID: Eq + Hash + Clone,
         T : Location<W>
 {
     ///
     /// If the `W` of the graph has a default value, allows for the adding of edges without a weight specified
 
-    pub fn add_edge_distance(&mut self, u: &'a ID, v: &'a ID) -> GraphResult {
-        let node1 = match self.get_node(u) {
-            None => { return Err(GraphError::IdDoesNotExist)},
-            Some(n) => {n},
-        };
-        let node2 = match self.get_node(v) {
-            None => { return Err(GraphError::IdDoesNotExist)},
-            Some(n) => {n},
-        };
+    pub fn add_edge_distance(&mut self, u: &'a ID, v: &'a ID) -> GraphResult<EdgeId, ID> {
+        require_endpoints(self, u, v, AddEdge)?;
+        let node1 = &self.nodes[u];
+        let node2 = &self.nodes[v];
 
         let weight = node1.distance_to(node2);
         self.add_edge_with(u, v, weight)
@@ -340,42 +576,68 @@ impl<'a, ID, W, T> HashGraph<ID, W, T>
 }
 
 
+///
+/// Panics if the node doesn't exist. [`Graph::try_get`] gives a [`GraphError`](crate::GraphError) naming the node instead
 impl<ID, W, T> Index<ID> for HashGraph<ID, W, T>
     where
-        ID: Eq + Hash + Copy,
-        T: Copy,
+        ID: Eq + Hash + Clone,
 {
     type Output = T;
 
     fn index(&self, index: ID) -> &Self::Output {
-        self.nodes.get(&index).unwrap().get_value()
+        match self.nodes.get(&index) {
+            Some(node) => node.get_value(),
+            None => panic!("Failed {}: node does not exist", Operation::Index),
+        }
     }
 }
 
+///
+/// Panics if the node doesn't exist. [`Graph::try_get_mut`] gives a [`GraphError`](crate::GraphError) naming the node instead
 impl<ID, W, T> IndexMut<ID> for HashGraph<ID, W, T>
     where
-        ID: Eq + Hash + Copy,
-        T: Copy,
+        ID: Eq + Hash + Clone,
 {
     fn index_mut(&mut self, index: ID) -> &mut Self::Output {
-        self.nodes.get_mut(&index).unwrap().get_value_mut()
+        match self.nodes.get_mut(&index) {
+            Some(node) => node.get_value_mut(),
+            None => panic!("Failed {}: node does not exist", Operation::Index),
+        }
     }
 }
 
+///
+/// Panics if the edge doesn't exist. [`Graph::try_get_weight`] gives a [`GraphError`](crate::GraphError) naming the edge instead
 impl<ID, W, T> Index<(ID, ID)> for HashGraph<ID, W, T>
     where
-        ID: Eq + Hash + Copy,
-        T: Copy,
+        ID: Eq + Hash + Clone,
 {
     type Output = W;
 
     fn index(&self, index: (ID, ID)) -> &Self::Output {
-        &self.adjacency[&index.0][&index.1]
+        match self.get_weight(&index.0, &index.1) {
+            Some(weight) => weight,
+            None => panic!("Failed {}: edge does not exist", Operation::Index),
+        }
+    }
+}
+
+///
+/// Panics if the edge doesn't exist. [`Graph::update_weight`] gives a [`GraphError`](crate::GraphError) naming the edge instead
+impl<ID, W, T> IndexMut<(ID, ID)> for HashGraph<ID, W, T>
+    where
+        ID: Eq + Hash + Clone,
+{
+    fn index_mut(&mut self, index: (ID, ID)) -> &mut Self::Output {
+        match self.get_weight_mut(&index.0, &index.1) {
+            Some(weight) => weight,
+            None => panic!("Failed {}: edge does not exist", Operation::Index),
+        }
     }
 }
 
 impl<'a, ID, W, T>  GraphRef<'a, ID, W, T, HashGraph<&'a ID, &'a W, &'a T>> for HashGraph<ID, W, T> where
-    ID: Eq + Hash + Copy, {
+    ID: Eq + Hash + Clone, {
     fn to_reference_graph(&'a self) -> HashGraph<&'a ID, &'a W, &'a T> {
         let mut output = HashGraph::new();
         {
@@ -388,7 +650,7 @@ impl<'a, ID, W, T>  GraphRef<'a, ID, W, T, HashGraph<&'a ID, &'a W, &'a T>> for
             }
         }
         {
-            for (ref id1, ref id2, weight) in self.edges() {
+            for (ref id1, ref id2, weight) in self.edges().map(EdgeRef::into_tuple) {
                 output.add_edge_with(id1, id2, weight);
             }
         }
@@ -426,27 +688,24 @@ fn compare_vectors_for_element_equality<T, R>(vec1: &Vec<T>, vec2: &Vec<R>) -> b
     }
 }
 
+///
+/// Two graphs are equal when they are isomorphic, with matching node values and edge weights.
+/// Use [`find_isomorphism`](crate::algorithms::find_isomorphism) to get the node mapping
 impl <ID, W, T, G, IDO, WO, TO> PartialEq<G> for HashGraph<ID,W,T>
-    where ID : Eq + Hash + Copy,
-          IDO: Eq,
+    where ID : Eq + Hash + Clone,
+          IDO: Eq + Hash,
           W : PartialEq<WO>,
           T : PartialEq<TO>,
           G: Graph<ID=IDO, Weight=WO, Value=TO>{
     fn eq(&self, other: &G) -> bool {
-        let this_nodes = self.nodes();
-        let other_nodes = other.nodes();
-        let mut nodes_map: HashMap<ID, IDO> = HashMap::new(); // self(ID) -> other(ID)
-
-        let this_nodes_set: Vec<&T> = this_nodes.iter().map(|n| n.get_value()).collect();
-        let other_nodes_set: Vec<&TO> = other_nodes.iter().map(|n| n.get_value()).collect();
+        let this_nodes_set: Vec<&T> = self.nodes().map(|n| n.get_value()).collect();
+        let other_nodes_set: Vec<&TO> = other.nodes().map(|n| n.get_value()).collect();
 
         if !compare_vectors_for_element_equality(&this_nodes_set, &other_nodes_set) {
             return false;
         }
 
-
-
-        unimplemented!()
+        is_isomorphic(self, other)
     }
 }
 
@@ -454,16 +713,19 @@ impl <ID, W, T, G, IDO, WO, TO> PartialEq<G> for HashGraph<ID,W,T>
 
 
 impl<ID, W, T> GraphReverse<ID, W, T> for HashGraph<ID, W, T> where
-    ID: Eq + Hash + Copy, {
+    ID: Eq + Hash + Clone, {
     fn into_reverse(self) -> Self {
         let mut output = Self::new();
         let (nodes, edges) = self.disassemble();
         for node in nodes {
-            output.add_node_with(*node.get_id(), node.value);
+            let (id, value) = node.into_tuple();
+            output.add_node_with(id, value)
+                .unwrap_or_else(|_| unreachable!("IDs are unique in the source graph"));
         }
 
         for (u, v, weight) in edges {
-            output.add_edge_with(&v, &u, weight);
+            output.add_edge_with(&v, &u, weight)
+                .unwrap_or_else(|_| unreachable!("Edges are unique in the source graph"));
         }
 
 
@@ -475,6 +737,9 @@ impl<ID, W, T> GraphReverse<ID, W, T> for HashGraph<ID, W, T> where
 #[cfg(test)]
 mod test {
     use crate::graph::hash_graph::compare_vectors_for_element_equality;
+    use crate::{HashGraph, Graph, GraphError, GraphTools, Operation, DuplicatePolicy};
+    use std::convert::TryFrom;
+    use std::iter::FromIterator;
 
     #[test]
     fn vector_element_equality() {
@@ -483,4 +748,252 @@ mod test {
 
         assert!(compare_vectors_for_element_equality(&vec1, &vec2));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn remove_node_drops_incident_edges() {
+        let mut g: HashGraph<usize, usize, ()> = HashGraph::new();
+        g.add_nodes(0..4).unwrap();
+        g.add_edge_with(&0, &1, 1).unwrap();
+        g.add_edge_with(&1, &2, 2).unwrap();
+        g.add_edge_with(&2, &1, 3).unwrap();
+        g.add_edge_with(&2, &3, 4).unwrap();
+
+        assert!(g.remove_node(&1).is_ok());
+        assert!(g.remove_node(&1).is_err(), "Node was already removed");
+        assert_eq!(g.num_nodes(), 3);
+        assert_eq!(g.num_edges(), 1);
+        assert_eq!(g.edges().count(), 1);
+        assert!(!g.contains_edge(&0, &1));
+        assert!(g.get_adjacent(&2).all(|&id| id != 1));
+        assert!(g.contains_edge(&2, &3));
+    }
+
+    #[test]
+    fn remove_edge_returns_weight() {
+        let mut g: HashGraph<usize, usize, ()> = HashGraph::new();
+        g.add_nodes(0..3).unwrap();
+        g.add_edge_with(&0, &1, 15).unwrap();
+        g.add_edge_with(&1, &2, 20).unwrap();
+
+        assert_eq!(g.remove_edge(&0, &1).unwrap(), 15);
+        assert!(g.remove_edge(&0, &1).is_err());
+        assert!(!g.contains_edge(&0, &1));
+        assert_eq!(g.num_edges(), 1);
+        assert_eq!(g.num_nodes(), 3);
+
+        g.clear();
+        assert_eq!(g.num_edges(), 0);
+        assert_eq!(g.num_nodes(), 0);
+        assert!(!g.contains_node(&2));
+    }
+
+    #[test]
+    fn incoming_edges() {
+        let mut g: HashGraph<usize, (), ()> = HashGraph::new();
+        g.add_nodes(0..4).unwrap();
+        g.add_edge(&0, &2).unwrap();
+        g.add_edge(&1, &2).unwrap();
+        g.add_edge(&2, &3).unwrap();
+
+        let mut incoming = g.get_incoming(&2).copied().collect::<Vec<_>>();
+        incoming.sort();
+        assert_eq!(incoming, vec![0, 1]);
+        assert_eq!(g.in_degree(&2), 2);
+        assert_eq!(g.out_degree(&2), 1);
+        assert_eq!(g.degree(&2), 3);
+
+        g.remove_edge(&0, &2).unwrap();
+        assert_eq!(g.in_degree(&2), 1);
+        g.remove_node(&2).unwrap();
+        assert_eq!(g.in_degree(&3), 0);
+        assert_eq!(g.out_degree(&1), 0);
+    }
+
+    #[test]
+    fn edge_ids() {
+        let mut g: HashGraph<usize, usize, ()> = HashGraph::new();
+        g.add_nodes(0..3).unwrap();
+        let first = g.add_edge_with(&0, &1, 15).unwrap();
+        let second = g.add_edge_with(&1, &2, 20).unwrap();
+        assert_ne!(first, second);
+
+        g.set_weight(&0, &1, 25).unwrap();
+        let edge = g.get_edge(first).unwrap();
+        assert_eq!(edge.into_tuple(), (&0, &1, &25), "IDs should survive weight updates");
+        assert_eq!(g.edge_id(&1, &2), Some(second));
+        assert_eq!(g.edge_endpoints(second), Some((&1, &2)));
+        assert_eq!(g.edge_weight(second), Some(&20));
+        assert_eq!(g.edges().map(|edge| edge.get_id()).collect::<Vec<_>>(), vec![first, second]);
+
+        assert_eq!(g.remove_edge_by_id(first).unwrap(), 25);
+        assert!(g.get_edge(first).is_none());
+        assert!(g.remove_edge_by_id(first).is_err());
+        assert!(!g.contains_edge(&0, &1));
+        assert_eq!(g.num_edges(), 1);
+
+        let third = g.add_edge_with(&0, &1, 5).unwrap();
+        assert_ne!(third, first, "Edge IDs are never reused");
+    }
+
+    #[test]
+    fn borrowed_lookups() {
+        let mut g: HashGraph<String, usize, usize> = HashGraph::new();
+        g.add_node_with("a".to_string(), 1).unwrap();
+        g.add_node_with("b".to_string(), 2).unwrap();
+        g.add_edge_with(&"a".to_string(), &"b".to_string(), 10).unwrap();
+
+        assert_eq!(g.get_node("a").map(|node| node.get_id().as_str()), Some("a"));
+        assert_eq!(g.get("b"), Some(&2));
+        assert!(g.contains_node("a"));
+        assert!(!g.contains_node("c"));
+        assert!(g.contains_edge("a", "b"));
+        assert_eq!(g.get_weight("a", "b"), Some(&10));
+
+        *g.get_mut("a").unwrap() += 5;
+        *g.get_weight_mut("a", "b").unwrap() += 5;
+        assert_eq!(g.get("a"), Some(&6));
+        assert_eq!(g[("a".to_string(), "b".to_string())], 15);
+
+        g.remove_node(&"a".to_string()).unwrap();
+        assert_eq!(g.num_edges(), 0);
+    }
+
+    #[test]
+    fn error_context() {
+        let mut g: HashGraph<usize, usize> = HashGraph::new();
+        g.add_nodes(0..2).unwrap();
+        g.add_edge_with(&0, &1, 1).unwrap();
+
+        assert_eq!(g.add_node(1), Err(GraphError::IdExists { id: 1, operation: Operation::AddNode }));
+        assert_eq!(g.add_edge_with(&0, &7, 1), Err(GraphError::IdDoesNotExist { id: 7, operation: Operation::AddEdge }));
+        assert_eq!(g.add_edge_with(&0, &1, 1), Err(GraphError::EdgeAlreadyExists { u: 0, v: 1, operation: Operation::AddEdge }));
+        let error = g.set_weight(&1, &0, 5).unwrap_err();
+        assert_eq!(error.operation(), Operation::UpdateWeight);
+        assert_eq!(error.to_string(), "Failed updating an edge weight: edge 1 -> 0 does not exist");
+
+        let boxed: Result<(), Box<dyn std::error::Error>> = g.remove_node(&3).map(|_| ()).map_err(Box::from);
+        assert_eq!(boxed.unwrap_err().to_string(), "Failed removing a node: node 3 does not exist");
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed indexing into the graph: edge does not exist")]
+    fn index_missing_edge() {
+        let mut g: HashGraph<usize, usize> = HashGraph::new();
+        g.add_nodes(0..2).unwrap();
+        g.add_edge_with(&0, &1, 1).unwrap();
+        let _ = g[(1, 0)];
+    }
+
+    #[test]
+    fn fallible_indexing() {
+        let mut g: HashGraph<usize, usize, char> = HashGraph::new();
+        g.add_node_with(0, 'a').unwrap();
+        g.add_node_with(1, 'b').unwrap();
+        g.add_edge_with(&0, &1, 4).unwrap();
+
+        assert_eq!(g.try_get(&1), Ok(&'b'));
+        assert_eq!(g.try_get(&2), Err(GraphError::IdDoesNotExist { id: 2, operation: Operation::Index }));
+        *g.try_get_mut(&0).unwrap() = 'c';
+        assert_eq!(g[0], 'c');
+        assert_eq!(g.try_get_weight(&0, &1), Ok(&4));
+        let error = g.try_get_weight(&1, &0).unwrap_err();
+        assert_eq!(error.to_string(), "Failed indexing into the graph: edge 1 -> 0 does not exist");
+    }
+
+    #[test]
+    fn index_without_debug_ids() {
+        #[derive(Clone, PartialEq, Eq, Hash)]
+        struct Stop(u32);
+
+        let mut g: HashGraph<Stop, usize, u32> = HashGraph::new();
+        assert!(g.add_node_with(Stop(0), 5).is_ok());
+        assert!(g.add_node_with(Stop(1), 6).is_ok());
+        assert!(g.add_edge_with(&Stop(0), &Stop(1), 2).is_ok());
+        g[Stop(1)] += 1;
+        assert_eq!(g[Stop(1)], 7);
+        assert_eq!(g[(Stop(0), Stop(1))], 2);
+    }
+
+    #[test]
+    fn auto_id_exhaustion() {
+        let mut g: HashGraph<u8> = HashGraph::new();
+        let ids = g.add_nodes_auto_id(vec![(); 256]).unwrap();
+        assert_eq!(ids.last(), Some(&255));
+        assert_eq!(g.add_node_auto_id(()), Err(GraphError::IdSpaceExhausted));
+    }
+
+    #[test]
+    fn try_from_reports_invalid_entries() {
+        let g = HashGraph::try_from((vec![(0, 'a'), (1, 'b')], vec![(0, 1, 5)])).unwrap();
+        assert_eq!(g.get_weight(&0, &1), Some(&5));
+
+        let duplicate = HashGraph::try_from((vec![(0, 'a'), (0, 'b')], vec![(0, 1, 5)]));
+        assert_eq!(duplicate.unwrap_err(), GraphError::IdExists { id: 0, operation: Operation::AddNode });
+
+        let errors = HashGraph::<usize, usize, char>::builder()
+            .nodes(vec![(0, 'a'), (0, 'b')])
+            .edges(vec![(0, 1, 5), (0, 0, 1), (0, 0, 2)])
+            .build_all()
+            .err()
+            .unwrap();
+        assert_eq!(errors, vec![
+            GraphError::IdExists { id: 0, operation: Operation::AddNode },
+            GraphError::IdDoesNotExist { id: 1, operation: Operation::AddEdge },
+            GraphError::EdgeAlreadyExists { u: 0, v: 0, operation: Operation::AddEdge },
+        ]);
+    }
+
+    #[test]
+    fn builder_duplicate_policies() {
+        let build = |policy| HashGraph::<usize, usize, char>::builder()
+            .duplicates(policy)
+            .node(0, 'a')
+            .node(0, 'b')
+            .edge(0, 0, 1)
+            .edge(0, 0, 2)
+            .build()
+            .unwrap();
+
+        let overwritten = build(DuplicatePolicy::Overwrite);
+        assert_eq!(overwritten.get(&0), Some(&'b'));
+        assert_eq!(overwritten.get_weight(&0, &0), Some(&2));
+
+        let kept = build(DuplicatePolicy::KeepFirst);
+        assert_eq!(kept.get(&0), Some(&'a'));
+        assert_eq!(kept.get_weight(&0, &0), Some(&1));
+    }
+
+    #[test]
+    fn collect_and_extend() {
+        let mut g: HashGraph<usize, usize, u8> = vec![(0, 1, 10), (1, 2, 20)].into_iter().collect();
+        assert_eq!(g.num_nodes(), 3);
+        assert_eq!(g.get(&2), Some(&0));
+
+        g.extend(vec![(1, 2, 25), (2, 3, 30)]);
+        g.extend(vec![(3, 9)]);
+        assert_eq!(g.num_edges(), 3);
+        assert_eq!(g.get_weight(&1, &2), Some(&25));
+        assert_eq!(g.get(&3), Some(&9));
+
+        let nodes = HashGraph::<&str, (), i32>::from_iter(vec![("a", 1), ("b", 2), ("a", 3)]);
+        assert_eq!(nodes.num_nodes(), 2);
+        assert_eq!(nodes.get(&"a"), Some(&3));
+    }
+
+    #[test]
+    fn equality_is_isomorphism() {
+        let g1: HashGraph<usize, u8, char> = HashGraph::try_from((
+            vec![(0, 'a'), (1, 'b'), (2, 'b')],
+            vec![(0, 1, 1), (0, 2, 2), (1, 2, 3)],
+        )).unwrap();
+        let g2: HashGraph<&str, u8, char> = HashGraph::try_from((
+            vec![("x", 'b'), ("y", 'a'), ("z", 'b')],
+            vec![("y", "z", 1), ("y", "x", 2), ("z", "x", 3)],
+        )).unwrap();
+        assert!(g1 == g2);
+
+        let mut g3 = g2.clone();
+        g3.set_weight(&"z", &"x", 4).unwrap();
+        assert!(g1 != g3);
+    }
+}
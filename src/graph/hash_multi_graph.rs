@@ -0,0 +1,623 @@
+use std::hash::Hash;
+use std::collections::{HashMap, HashSet, BTreeMap};
+use crate::{Node, EdgeId, EdgeRef, GraphRef, GraphReverse};
+use crate::graph::{Graph, GraphLookup, GraphResult, Operation, require_endpoints};
+use crate::graph::GraphError::{IdExists, IdDoesNotExist, EdgeDoesNotExist, EdgeIdDoesNotExist};
+use crate::graph::Operation::{AddNode, RemoveNode, AddEdge, RemoveEdge, UpdateWeight};
+use std::borrow::Borrow;
+use std::ops::{Index, IndexMut};
+use std::fmt::{Debug, Formatter, Display};
+
+///
+/// A graph that allows any number of parallel edges between the same two nodes.
+///
+/// Every parallel edge keeps its own weight. Methods that operate on a single `u -> v` edge,
+/// such as `get_weight` and `update_weight`, use the oldest of the parallel edges, while
+/// `get_weights` and `edges` visit every one of them. Edges are visited in the order they were added.
+///
+/// `ID` must be impl `Hash`, `Eq`, and `Clone`
+pub struct HashMultiGraph<ID = usize, W = (), T = ()>
+    where
+        ID: Eq + Hash + Clone,
+{
+    adjacency: HashMap<ID, HashMap<ID, Vec<(EdgeId, W)>>>,
+    incoming: HashMap<ID, HashSet<ID>>,
+    nodes: HashMap<ID, Node<ID, T>>,
+    edges: BTreeMap<EdgeId, (ID, ID)>,
+    next_edge: usize,
+    num_nodes: usize,
+    num_edges: usize,
+}
+
+impl<ID, W, T> Display for HashMultiGraph<ID, W, T> where
+    ID: Eq + Hash + Clone, {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HashMultiGraph{{ size = {} }}", self.num_nodes)
+    }
+}
+
+impl<ID, W, T> Debug for HashMultiGraph<ID, W, T> where
+    ID: Eq + Hash + Clone, {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<ID, W, T> Default for HashMultiGraph<ID, W, T>
+    where ID: Eq + Hash + Clone {
+    fn default() -> Self {
+        Self {
+            adjacency: Default::default(),
+            incoming: Default::default(),
+            nodes: Default::default(),
+            edges: Default::default(),
+            next_edge: 0,
+            num_nodes: 0,
+            num_edges: 0
+        }
+    }
+}
+
+impl<ID, W, T> Graph for HashMultiGraph<ID, W, T>
+    where
+        ID: Eq + Hash + Clone,
+{
+    type ID = ID;
+    type Weight = W;
+    type Value = T;
+
+    fn get_node(&self, id: &ID) -> Option<&Node<ID, T>> {
+        self.nodes.get(id)
+    }
+
+    fn get_node_mut(&mut self, id: &ID) -> Option<&mut Node<ID, T>> {
+        self.nodes.get_mut(id)
+    }
+
+    fn add_node_with(&mut self, id: ID, value: T) -> GraphResult<(), ID> {
+        if self.nodes.contains_key(&id) {
+            return Err(IdExists { id, operation: AddNode });
+        }
+
+        self.nodes.insert(id.clone(), Node::new(id, value));
+        self.num_nodes += 1;
+        Ok(())
+    }
+
+    fn remove_node(&mut self, id: &ID) -> GraphResult<Node<ID, T>, ID> {
+        let node = match self.nodes.remove(id) {
+            None => { return Err(IdDoesNotExist { id: id.clone(), operation: RemoveNode }); },
+            Some(node) => node,
+        };
+        self.num_nodes -= 1;
+
+        if let Some(outgoing) = self.adjacency.remove(id) {
+            for (v, parallel) in outgoing {
+                if let Some(set) = self.incoming.get_mut(&v) {
+                    set.remove(id);
+                }
+                self.remove_parallel(&parallel);
+            }
+        }
+        if let Some(incoming) = self.incoming.remove(id) {
+            for u in incoming {
+                if let Some(parallel) = self.adjacency.get_mut(&u).and_then(|map| map.remove(id)) {
+                    self.remove_parallel(&parallel);
+                }
+            }
+        }
+
+        Ok(node)
+    }
+
+    fn contains_node(&self, id: &ID) -> bool {
+        self.nodes.contains_key(id)
+    }
+
+    ///
+    /// Adds another `u -> v` edge. Unlike other graphs, this never fails because the edge already exists
+    fn add_edge_with(&mut self, u: &ID, v: &ID, weight: W) -> GraphResult<EdgeId, ID> {
+        require_endpoints(self, u, v, AddEdge)?;
+        let edge = EdgeId::new(self.next_edge);
+        self.next_edge += 1;
+        self.adjacency.entry(u.clone()).or_default().entry(v.clone()).or_default().push((edge, weight));
+        self.incoming.entry(v.clone()).or_default().insert(u.clone());
+        self.edges.insert(edge, (u.clone(), v.clone()));
+        self.num_edges += 1;
+        Ok(edge)
+    }
+
+    ///
+    /// Removes the oldest `u -> v` edge, the same one `get_weight` and `edge_id` see, returning its weight
+    fn remove_edge(&mut self, u: &ID, v: &ID) -> GraphResult<W, ID> {
+        match self.adjacency.get(u).and_then(|map| map.get(v)).and_then(|parallel| parallel.first()) {
+            None => Err(EdgeDoesNotExist { u: u.clone(), v: v.clone(), operation: RemoveEdge }),
+            Some(&(edge, _)) => self.remove_edge_by_id(edge),
+        }
+    }
+
+    fn remove_edge_by_id(&mut self, edge: EdgeId) -> GraphResult<W, ID> {
+        let (u, v) = match self.edges.remove(&edge) {
+            None => { return Err(EdgeIdDoesNotExist { edge, operation: RemoveEdge }); },
+            Some(endpoints) => endpoints,
+        };
+        let map = self.adjacency.get_mut(&u).expect("Edge index and adjacency should agree");
+        let parallel = map.get_mut(&v).expect("Edge index and adjacency should agree");
+        let position = parallel.iter().position(|(id, _)| *id == edge)
+            .expect("Edge index and adjacency should agree");
+        let (_, weight) = parallel.remove(position);
+        if parallel.is_empty() {
+            map.remove(&v);
+            if let Some(set) = self.incoming.get_mut(&v) {
+                set.remove(&u);
+            }
+        }
+
+        self.num_edges -= 1;
+        Ok(weight)
+    }
+
+    fn get_edge(&self, edge: EdgeId) -> Option<EdgeRef<'_, ID, W>> {
+        let (u, v) = self.edges.get(&edge)?;
+        self.adjacency[u][v].iter()
+            .find(|(id, _)| *id == edge)
+            .map(|(_, weight)| EdgeRef::new(edge, u, v, weight))
+    }
+
+    fn edge_id(&self, u: &ID, v: &ID) -> Option<EdgeId> {
+        self.get_edges(u, v).next().map(|edge| edge.get_id())
+    }
+
+    fn get_edges<'a>(&'a self, u: &ID, v: &ID) -> impl Iterator<Item=EdgeRef<'a, ID, W>> + use<'a, ID, W, T> {
+        self.adjacency.get_key_value(u)
+            .and_then(|(u, map)| map.get_key_value(v).map(|(v, parallel)| (u, v, parallel)))
+            .into_iter()
+            .flat_map(|(u, v, parallel)| parallel.iter().map(move |(edge, weight)| EdgeRef::new(*edge, u, v, weight)))
+    }
+
+    fn contains_edge(&self, u: &ID, v: &ID) -> bool {
+        match self.adjacency.get(u) {
+            None => false,
+            Some(map) => map.contains_key(v),
+        }
+    }
+
+    fn get_weight(&self, u: &ID, v: &ID) -> Option<&W> {
+        self.get_weights(u, v).next()
+    }
+
+    fn update_weight<R, F>(&mut self, u: &ID, v: &ID, f: F) -> GraphResult<R, ID>
+        where F: FnOnce(&mut W) -> R {
+        match self.get_weight_mut(u, v) {
+            None => Err(EdgeDoesNotExist { u: u.clone(), v: v.clone(), operation: UpdateWeight }),
+            Some(weight) => Ok(f(weight)),
+        }
+    }
+
+    fn get_adjacent<'a>(&'a self, node: &ID) -> impl Iterator<Item=&'a ID> + use<'a, ID, W, T> {
+        self.adjacency.get(node).into_iter().flat_map(|map| map.keys())
+    }
+
+    fn get_incoming<'a>(&'a self, node: &ID) -> impl Iterator<Item=&'a ID> + use<'a, ID, W, T> {
+        self.incoming.get(node).into_iter().flatten()
+    }
+
+    fn in_degree(&self, node: &ID) -> usize {
+        self.get_incoming(node)
+            .map(|u| self.adjacency[u][node].len())
+            .sum()
+    }
+
+    fn out_degree(&self, node: &ID) -> usize {
+        self.adjacency.get(node).map_or(0, |map| map.values().map(Vec::len).sum())
+    }
+
+    fn nodes(&self) -> impl Iterator<Item=&Node<Self::ID, Self::Value>> {
+        self.nodes.values()
+    }
+
+    fn edges(&self) -> impl Iterator<Item=EdgeRef<'_, Self::ID, Self::Weight>> {
+        self.edges.iter()
+            .map(move |(edge, (u, v))| {
+                let (_, weight) = self.adjacency[u][v].iter()
+                    .find(|(id, _)| id == edge)
+                    .expect("Edge index and adjacency should agree");
+                EdgeRef::new(*edge, u, v, weight)
+            })
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.num_nodes
+    }
+
+    fn num_edges(&self) -> usize {
+        self.num_edges
+    }
+
+    fn take_nodes(self) -> Vec<Node<Self::ID, Self::Value>> {
+        self.nodes.into_values().collect()
+    }
+
+    fn clear(&mut self) {
+        self.adjacency.clear();
+        self.incoming.clear();
+        self.nodes.clear();
+        self.edges.clear();
+        self.num_nodes = 0;
+        self.num_edges = 0;
+    }
+}
+
+impl<ID, W, T> HashMultiGraph<ID, W, T>
+    where
+        ID: Eq + Hash + Clone {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Looks up a node by any borrowed form of its ID, such as a `&str` for a graph keyed by `String`
+    pub fn get_node<Q>(&self, id: &Q) -> Option<&Node<ID, T>>
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.nodes.get(id)
+    }
+
+    pub fn get_node_mut<Q>(&mut self, id: &Q) -> Option<&mut Node<ID, T>>
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.nodes.get_mut(id)
+    }
+
+    pub fn get<Q>(&self, id: &Q) -> Option<&T>
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.get_node(id).map(|node| node.get_value())
+    }
+
+    pub fn get_mut<Q>(&mut self, id: &Q) -> Option<&mut T>
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.get_node_mut(id).map(|node| node.get_value_mut())
+    }
+
+    pub fn contains_node<Q>(&self, id: &Q) -> bool
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.nodes.contains_key(id)
+    }
+
+    pub fn contains_edge<Q>(&self, u: &Q, v: &Q) -> bool
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.adjacency.get(u).is_some_and(|map| map.contains_key(v))
+    }
+
+    pub fn get_weight<Q>(&self, u: &Q, v: &Q) -> Option<&W>
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.adjacency.get(u)
+            .and_then(|map| map.get(v))
+            .and_then(|parallel| parallel.first())
+            .map(|(_, weight)| weight)
+    }
+
+    pub fn get_weight_mut<Q>(&mut self, u: &Q, v: &Q) -> Option<&mut W>
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.get_weights_mut(u, v).next()
+    }
+
+    ///
+    /// Gets mutable access to the weights of every parallel `u -> v` edge, oldest first
+    pub fn get_weights_mut<Q>(&mut self, u: &Q, v: &Q) -> impl Iterator<Item=&mut W>
+        where ID: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.adjacency.get_mut(u)
+            .and_then(|map| map.get_mut(v))
+            .into_iter()
+            .flatten()
+            .map(|(_, weight)| weight)
+    }
+
+    ///
+    /// Gets mutable access to the weight of the edge with the given ID
+    pub fn edge_weight_mut(&mut self, edge: EdgeId) -> Option<&mut W> {
+        let (u, v) = self.edges.get(&edge)?;
+        self.adjacency.get_mut(u)
+            .and_then(|map| map.get_mut(v))
+            .and_then(|parallel| parallel.iter_mut().find(|(id, _)| *id == edge))
+            .map(|(_, weight)| weight)
+    }
+
+    fn remove_parallel(&mut self, parallel: &[(EdgeId, W)]) {
+        for (edge, _) in parallel {
+            self.edges.remove(edge);
+        }
+        self.num_edges -= parallel.len();
+    }
+
+    ///
+    /// The number of parallel `u -> v` edges
+    pub fn edge_multiplicity(&self, u: &ID, v: &ID) -> usize {
+        self.adjacency.get(u)
+            .and_then(|map| map.get(v))
+            .map_or(0, Vec::len)
+    }
+
+    ///
+    /// Splits the graph into its nodes and its edges, with the edges in the order they were added
+    #[allow(clippy::type_complexity)]
+    pub fn disassemble(mut self) -> (Vec<Node<ID, T>>, Vec<(ID, ID, W)>) {
+        let adj = std::mem::take(&mut self.adjacency);
+        let mut edges: Vec<_> = adj.into_iter()
+            .flat_map(|(u, map)| {
+                map.into_iter().flat_map(move |(v, parallel)| {
+                    let u = u.clone();
+                    parallel.into_iter().map(move |(edge, weight)| (edge, u.clone(), v.clone(), weight))
+                })
+            })
+            .collect();
+        edges.sort_by_key(|(edge, ..)| *edge);
+        let edges = edges.into_iter().map(|(_, u, v, weight)| (u, v, weight)).collect();
+
+        let nodes = self.take_nodes();
+
+        (nodes, edges)
+    }
+
+    pub fn as_reverse(&self) -> HashMultiGraph<&ID, &W, &T> {
+        self.to_reference_graph().into_reverse()
+    }
+}
+
+impl<ID, W, T> HashMultiGraph<ID, W, T>
+    where
+        ID: Eq + Hash + Clone,
+        T: Copy,
+{
+    pub fn add_nodes_with<I>(&mut self, id: I, value: T) -> GraphResult<(), ID>
+        where
+            I: Iterator<Item = ID>,
+    {
+        for n in id {
+            self.add_node_with(n, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl<ID, W, T> HashMultiGraph<ID, W, T>
+    where
+        ID: Eq + Hash + Clone,
+        T: Default,
+{
+    pub fn add_nodes<I>(&mut self, id: I) -> GraphResult<(), ID>
+        where
+            I: Iterator<Item = ID>,
+    {
+        for n in id {
+            self.add_node_with(n, T::default())?;
+        }
+        Ok(())
+    }
+
+    pub fn add_node(&mut self, id: ID) -> GraphResult<(), ID> {
+        self.add_node_with(id, T::default())
+    }
+}
+
+impl<ID, W, T> HashMultiGraph<ID, W, T>
+    where
+        ID: Eq + Hash + Clone,
+        W: Default,
+{
+    ///
+    /// If the `W` of the graph has a default value, allows for the adding of edges without a weight specified
+    pub fn add_edge(&mut self, u: &ID, v: &ID) -> GraphResult<EdgeId, ID> {
+        self.add_edge_with(u, v, Default::default())
+    }
+}
+
+
+impl<ID, W, T, Q> GraphLookup<Q> for HashMultiGraph<ID, W, T>
+    where
+        ID: Eq + Hash + Clone,
+        ID: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+{
+    fn lookup_node(&self, id: &Q) -> Option<&Node<ID, T>> {
+        self.get_node(id)
+    }
+
+    fn lookup_node_mut(&mut self, id: &Q) -> Option<&mut Node<ID, T>> {
+        self.get_node_mut(id)
+    }
+
+    fn lookup_weight(&self, u: &Q, v: &Q) -> Option<&W> {
+        self.get_weight(u, v)
+    }
+}
+
+impl<ID, W, T> Clone for HashMultiGraph<ID, W, T>
+    where
+        ID: Eq + Hash + Clone,
+        W: Clone,
+        T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            adjacency: self.adjacency.clone(),
+            incoming: self.incoming.clone(),
+            nodes: self.nodes.clone(),
+            edges: self.edges.clone(),
+            next_edge: self.next_edge,
+            num_nodes: self.num_nodes,
+            num_edges: self.num_edges,
+        }
+    }
+}
+
+///
+/// Panics if the node doesn't exist. [`Graph::try_get`] gives a [`GraphError`](crate::GraphError) naming the node instead
+impl<ID, W, T> Index<ID> for HashMultiGraph<ID, W, T>
+    where
+        ID: Eq + Hash + Clone,
+{
+    type Output = T;
+
+    fn index(&self, index: ID) -> &Self::Output {
+        match self.nodes.get(&index) {
+            Some(node) => node.get_value(),
+            None => panic!("Failed {}: node does not exist", Operation::Index),
+        }
+    }
+}
+
+///
+/// Panics if the node doesn't exist. [`Graph::try_get_mut`] gives a [`GraphError`](crate::GraphError) naming the node instead
+impl<ID, W, T> IndexMut<ID> for HashMultiGraph<ID, W, T>
+    where
+        ID: Eq + Hash + Clone,
+{
+    fn index_mut(&mut self, index: ID) -> &mut Self::Output {
+        match self.nodes.get_mut(&index) {
+            Some(node) => node.get_value_mut(),
+            None => panic!("Failed {}: node does not exist", Operation::Index),
+        }
+    }
+}
+
+///
+/// Indexes the oldest of the parallel `u -> v` edges, and panics if there isn't one. [`Graph::try_get_weight`] gives a [`GraphError`](crate::GraphError) naming the edge instead
+impl<ID, W, T> Index<(ID, ID)> for HashMultiGraph<ID, W, T>
+    where
+        ID: Eq + Hash + Clone,
+{
+    type Output = W;
+
+    fn index(&self, index: (ID, ID)) -> &Self::Output {
+        match self.get_weight(&index.0, &index.1) {
+            Some(weight) => weight,
+            None => panic!("Failed {}: edge does not exist", Operation::Index),
+        }
+    }
+}
+
+///
+/// Panics if the edge doesn't exist. [`Graph::update_weight`] gives a [`GraphError`](crate::GraphError) naming the edge instead
+impl<ID, W, T> IndexMut<(ID, ID)> for HashMultiGraph<ID, W, T>
+    where
+        ID: Eq + Hash + Clone,
+{
+    fn index_mut(&mut self, index: (ID, ID)) -> &mut Self::Output {
+        match self.get_weight_mut(&index.0, &index.1) {
+            Some(weight) => weight,
+            None => panic!("Failed {}: edge does not exist", Operation::Index),
+        }
+    }
+}
+
+impl<'a, ID, W, T> GraphRef<'a, ID, W, T, HashMultiGraph<&'a ID, &'a W, &'a T>> for HashMultiGraph<ID, W, T> where
+    ID: Eq + Hash + Clone, {
+    fn to_reference_graph(&'a self) -> HashMultiGraph<&'a ID, &'a W, &'a T> {
+        let mut output = HashMultiGraph::new();
+        for node in self.nodes() {
+            output.add_node_with(node.get_id(), node.get_value())
+                .unwrap_or_else(|_| unreachable!("IDs are unique in the source graph"));
+        }
+        for (id1, id2, weight) in self.edges().map(EdgeRef::into_tuple) {
+            output.add_edge_with(&id1, &id2, weight)
+                .unwrap_or_else(|_| unreachable!("Both ends are in the graph"));
+        }
+
+        output
+    }
+}
+
+impl<ID, W, T> GraphReverse<ID, W, T> for HashMultiGraph<ID, W, T> where
+    ID: Eq + Hash + Clone, {
+    fn into_reverse(self) -> Self {
+        let mut output = Self::new();
+        let (nodes, edges) = self.disassemble();
+        for node in nodes {
+            let (id, value) = node.into_tuple();
+            output.add_node_with(id, value)
+                .unwrap_or_else(|_| unreachable!("IDs are unique in the source graph"));
+        }
+
+        for (u, v, weight) in edges {
+            output.add_edge_with(&v, &u, weight)
+                .unwrap_or_else(|_| unreachable!("Both ends are in the graph"));
+        }
+
+        output
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::{HashMultiGraph, Graph, GraphRef, GraphReverse, EdgeRef};
+
+    #[test]
+    fn parallel_edges() {
+        let mut g: HashMultiGraph<usize, usize, ()> = HashMultiGraph::new();
+        g.add_nodes(0..3).unwrap();
+        g.add_edge_with(&0, &1, 10).unwrap();
+        g.add_edge_with(&0, &1, 3).unwrap();
+        g.add_edge_with(&0, &1, 7).unwrap();
+        g.add_edge_with(&1, &2, 1).unwrap();
+
+        assert_eq!(g.num_edges(), 4);
+        assert_eq!(g.edges().count(), 4);
+        assert_eq!(g.edge_multiplicity(&0, &1), 3);
+        assert_eq!(g.get_weights(&0, &1).collect::<Vec<_>>(), vec![&10, &3, &7]);
+        assert_eq!(g.get_weight(&0, &1), Some(&10), "Oldest edge is used for single edge access");
+        assert_eq!(g.get_adjacent(&0).count(), 1, "Adjacent nodes are only listed once");
+        assert_eq!(g.out_degree(&0), 3);
+        assert_eq!(g.in_degree(&1), 3);
+        assert_eq!(g.degree(&1), 4);
+    }
+
+    #[test]
+    fn remove_parallel_edges() {
+        let mut g: HashMultiGraph<usize, usize, ()> = HashMultiGraph::new();
+        g.add_nodes(0..3).unwrap();
+        g.add_edge_with(&0, &1, 10).unwrap();
+        g.add_edge_with(&0, &1, 3).unwrap();
+        g.add_edge_with(&2, &1, 4).unwrap();
+
+        assert_eq!(g.remove_edge(&0, &1).unwrap(), 10, "The oldest edge goes first");
+        assert!(g.contains_edge(&0, &1));
+        assert_eq!(g.get_weight(&0, &1), Some(&3));
+        assert_eq!(g.remove_edge(&0, &1).unwrap(), 3);
+        assert!(!g.contains_edge(&0, &1));
+        assert!(g.remove_edge(&0, &1).is_err());
+        assert_eq!(g.get_incoming(&1).collect::<Vec<_>>(), vec![&2]);
+
+        g.add_edge_with(&1, &2, 1).unwrap();
+        g.add_edge_with(&1, &2, 2).unwrap();
+        g.remove_node(&1).unwrap();
+        assert_eq!(g.num_edges(), 0);
+        assert_eq!(g.out_degree(&2), 0);
+    }
+
+    #[test]
+    fn ordered_edges_and_reverse() {
+        let mut g: HashMultiGraph<usize, usize, ()> = HashMultiGraph::new();
+        g.add_nodes(0..4).unwrap();
+        for (u, v, w) in [(3, 0, 1), (0, 1, 2), (2, 3, 3), (0, 1, 4), (1, 2, 5)] {
+            g.add_edge_with(&u, &v, w).unwrap();
+        }
+
+        let weights: Vec<_> = g.edges().map(|edge| *edge.get_weight()).collect();
+        assert_eq!(weights, vec![1, 2, 3, 4, 5], "Edges are visited in the order they were added");
+        assert_eq!(g[(0, 1)], 2);
+        g[(0, 1)] += 10;
+        assert_eq!(g.get_weights(&0, &1).collect::<Vec<_>>(), vec![&12, &4]);
+
+        let references = g.to_reference_graph();
+        assert_eq!(references.num_edges(), 5);
+        assert_eq!(references.edge_multiplicity(&&0, &&1), 2);
+
+        let reverse = g.as_reverse();
+        assert_eq!(reverse.get_weights(&&1, &&0).collect::<Vec<_>>(), vec![&&12, &&4]);
+        assert!(!reverse.contains_edge(&&0, &&1));
+
+        let edges: Vec<_> = g.into_reverse().edges().map(EdgeRef::into_tuple).map(|(u, v, w)| (*u, *v, *w)).collect();
+        assert_eq!(edges, vec![(0, 3, 1), (1, 0, 12), (3, 2, 3), (1, 0, 4), (2, 1, 5)]);
+    }
+}
@@ -0,0 +1,572 @@
+use crate::{Graph, GraphLookup, GraphResult, GraphError, Operation, EdgeId, EdgeRef, Node};
+use crate::GraphError::{IdExists, IdDoesNotExist, EdgeDoesNotExist};
+use crate::Operation::{AddNode, RemoveNode, AddEdge, RemoveEdge, UpdateWeight};
+use crate::pathing::PathFinder;
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter, Result};
+use std::hash::Hash;
+use num_traits::Num;
+
+///
+/// A two way mapping between external keys and dense `usize` IDs.
+///
+/// IDs are handed out in the order keys are first seen, starting at 0, and a key keeps its ID
+/// for the lifetime of the interner
+#[derive(Clone)]
+pub struct Interner<K>
+    where K: Eq + Hash + Clone {
+    keys: Vec<K>,
+    ids: HashMap<K, usize>,
+}
+
+impl<K> Default for Interner<K>
+    where K: Eq + Hash + Clone {
+    fn default() -> Self {
+        Self {
+            keys: vec![],
+            ids: Default::default(),
+        }
+    }
+}
+
+impl<K> Interner<K>
+    where K: Eq + Hash + Clone {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Gets the ID of `key`, giving it the next free ID if it hasn't been seen before
+    pub fn intern(&mut self, key: K) -> usize {
+        if let Some(id) = self.ids.get(&key) {
+            return *id;
+        }
+
+        let id = self.keys.len();
+        self.keys.push(key.clone());
+        self.ids.insert(key, id);
+        id
+    }
+
+    pub fn get_id<Q>(&self, key: &Q) -> Option<usize>
+        where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.ids.get(key).copied()
+    }
+
+    pub fn get_key(&self, id: usize) -> Option<&K> {
+        self.keys.get(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+///
+/// Wraps a graph or path finder that uses dense `usize` IDs so that it can be used with any
+/// hashable key instead.
+///
+/// Keys are translated to IDs on the way in and back to keys on the way out, so algorithms on
+/// the inner value only ever see `usize` IDs.
+///
+/// It also implements [`Graph`] with the inner graph's `usize` IDs, so it can be passed to the
+/// generic algorithms, whose results can be turned back into keys with [`Interned::key_of`].
+/// [`GraphLookup`] finds nodes and edges by key from generic code
+pub struct Interned<K, G>
+    where K: Eq + Hash + Clone {
+    interner: Interner<K>,
+    inner: G,
+}
+
+impl<K, G> Interned<K, G>
+    where K: Eq + Hash + Clone {
+
+    pub fn interner(&self) -> &Interner<K> {
+        &self.interner
+    }
+
+    pub fn inner(&self) -> &G {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> (Interner<K>, G) {
+        (self.interner, self.inner)
+    }
+
+    ///
+    /// Swaps the inner value for something built from it while keeping the same keys, such as
+    /// turning the inner graph into a path finder
+    pub fn map_inner<P, F>(self, f: F) -> Interned<K, P>
+        where F: FnOnce(G) -> P {
+        Interned {
+            interner: self.interner,
+            inner: f(self.inner),
+        }
+    }
+
+    pub fn id_of<Q>(&self, key: &Q) -> Option<usize>
+        where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.interner.get_id(key)
+    }
+
+    pub fn key_of(&self, id: usize) -> Option<&K> {
+        self.interner.get_key(id)
+    }
+
+    fn endpoints<Q>(&self, u: &Q, v: &Q) -> Option<(usize, usize)>
+        where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        Some((self.id_of(u)?, self.id_of(v)?))
+    }
+
+    fn key(&self, id: &usize) -> &K {
+        self.interner.get_key(*id).expect("Every ID in the inner graph was interned")
+    }
+
+    fn lookup_id<Q>(&self, key: &Q, operation: Operation) -> GraphResult<usize, K>
+        where K: Borrow<Q>, Q: Hash + Eq + ToOwned<Owned=K> + ?Sized {
+        self.id_of(key).ok_or_else(|| IdDoesNotExist { id: key.to_owned(), operation })
+    }
+
+    ///
+    /// Like `endpoints`, but an unknown key means the edge doesn't exist
+    fn edge_endpoints_of<Q>(&self, u: &Q, v: &Q, operation: Operation) -> GraphResult<(usize, usize), K>
+        where K: Borrow<Q>, Q: Hash + Eq + ToOwned<Owned=K> + ?Sized {
+        self.endpoints(u, v).ok_or_else(|| EdgeDoesNotExist { u: u.to_owned(), v: v.to_owned(), operation })
+    }
+
+    ///
+    /// Swaps the inner graph's IDs in an error for the keys they came from
+    fn translate(&self, error: GraphError<usize>) -> GraphError<K> {
+        error.map_ids(|id| self.key(&id).clone())
+    }
+}
+
+impl<K, W, T, G> Interned<K, G>
+    where K: Eq + Hash + Clone,
+          G: Graph<ID=usize, Weight=W, Value=T> {
+
+    ///
+    /// Wraps an empty graph
+    pub fn new(graph: G) -> Self {
+        debug_assert_eq!(graph.num_nodes(), 0, "The inner graph should start empty");
+        Self {
+            interner: Interner::new(),
+            inner: graph,
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&T>
+        where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.inner.get(&self.id_of(key)?)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut T>
+        where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let id = self.id_of(key)?;
+        self.inner.get_mut(&id)
+    }
+
+    ///
+    /// Adds a node for `key`, returning the dense ID it was given in the inner graph
+    pub fn add_node_with(&mut self, key: K, value: T) -> GraphResult<usize, K> {
+        if self.contains_node(&key) {
+            return Err(IdExists { id: key, operation: AddNode });
+        }
+        // The key is only interned once the inner graph has taken the node
+        let id = self.interner.get_id(&key).unwrap_or_else(|| self.interner.len());
+        if let Err(error) = self.inner.add_node_with(id, value) {
+            return Err(error.map_ids(|_| key.clone()));
+        }
+        self.interner.intern(key);
+        Ok(id)
+    }
+
+    pub fn remove_node<Q>(&mut self, key: &Q) -> GraphResult<T, K>
+        where K: Borrow<Q>, Q: Hash + Eq + ToOwned<Owned=K> + ?Sized {
+        let id = self.lookup_id(key, RemoveNode)?;
+        match self.inner.remove_node(&id) {
+            Ok(node) => Ok(node.into_tuple().1),
+            Err(e) => Err(self.translate(e)),
+        }
+    }
+
+    pub fn contains_node<Q>(&self, key: &Q) -> bool
+        where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.id_of(key).is_some_and(|id| self.inner.contains_node(&id))
+    }
+
+    pub fn add_edge_with<Q>(&mut self, u: &Q, v: &Q, weight: W) -> GraphResult<EdgeId, K>
+        where K: Borrow<Q>, Q: Hash + Eq + ToOwned<Owned=K> + ?Sized {
+        let u = self.lookup_id(u, AddEdge)?;
+        let v = self.lookup_id(v, AddEdge)?;
+        self.inner.add_edge_with(&u, &v, weight).map_err(|e| self.translate(e))
+    }
+
+    pub fn remove_edge<Q>(&mut self, u: &Q, v: &Q) -> GraphResult<W, K>
+        where K: Borrow<Q>, Q: Hash + Eq + ToOwned<Owned=K> + ?Sized {
+        let (u, v) = self.edge_endpoints_of(u, v, RemoveEdge)?;
+        self.inner.remove_edge(&u, &v).map_err(|e| self.translate(e))
+    }
+
+    pub fn contains_edge<Q>(&self, u: &Q, v: &Q) -> bool
+        where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.endpoints(u, v).is_some_and(|(u, v)| self.inner.contains_edge(&u, &v))
+    }
+
+    pub fn get_weight<Q>(&self, u: &Q, v: &Q) -> Option<&W>
+        where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        let (u, v) = self.endpoints(u, v)?;
+        self.inner.get_weight(&u, &v)
+    }
+
+    pub fn update_weight<Q, R, F>(&mut self, u: &Q, v: &Q, f: F) -> GraphResult<R, K>
+        where K: Borrow<Q>, Q: Hash + Eq + ToOwned<Owned=K> + ?Sized,
+              F: FnOnce(&mut W) -> R {
+        let (u, v) = self.edge_endpoints_of(u, v, UpdateWeight)?;
+        self.inner.update_weight(&u, &v, f).map_err(|e| self.translate(e))
+    }
+
+    pub fn set_weight<Q>(&mut self, u: &Q, v: &Q, weight: W) -> GraphResult<W, K>
+        where K: Borrow<Q>, Q: Hash + Eq + ToOwned<Owned=K> + ?Sized {
+        self.update_weight(u, v, |old| std::mem::replace(old, weight))
+    }
+
+    ///
+    /// Gets the `(u, v)` keys of the edge with the given ID
+    pub fn edge_endpoints(&self, edge: EdgeId) -> Option<(&K, &K)> {
+        self.inner.edge_endpoints(edge).map(|(u, v)| (self.key(u), self.key(v)))
+    }
+
+    pub fn get_adjacent<Q>(&self, key: &Q) -> impl Iterator<Item=&K>
+        where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.id_of(key)
+            .into_iter()
+            .flat_map(move |id| self.inner.get_adjacent(&id))
+            .map(move |id| self.key(id))
+    }
+
+    pub fn get_incoming<Q>(&self, key: &Q) -> impl Iterator<Item=&K>
+        where K: Borrow<Q>, Q: Hash + Eq + ?Sized {
+        self.id_of(key)
+            .into_iter()
+            .flat_map(move |id| self.inner.get_incoming(&id))
+            .map(move |id| self.key(id))
+    }
+
+    ///
+    /// Iterates over every node as `(key, value)`
+    pub fn nodes<'a>(&'a self) -> impl Iterator<Item=(&'a K, &'a T)> where T: 'a {
+        self.inner.nodes().map(move |node| (self.key(node.get_id()), node.get_value()))
+    }
+
+    ///
+    /// Iterates over every edge as `(u, v, weight)`, alongside its ID in the inner graph
+    pub fn edges<'a>(&'a self) -> impl Iterator<Item=(EdgeId, &'a K, &'a K, &'a W)> where W: 'a {
+        self.inner.edges().map(move |edge: EdgeRef<'a, usize, W>| {
+            (edge.get_id(), self.key(edge.get_source()), self.key(edge.get_target()), edge.get_weight())
+        })
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.inner.num_nodes()
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.inner.num_edges()
+    }
+}
+
+impl<K, W, T, G> Interned<K, G>
+    where K: Eq + Hash + Clone,
+          G: Graph<ID=usize, Weight=W, Value=T>,
+          T: Default {
+
+    pub fn add_node(&mut self, key: K) -> GraphResult<usize, K> {
+        self.add_node_with(key, T::default())
+    }
+}
+
+impl<K, W, T, G> Interned<K, G>
+    where K: Eq + Hash + Clone,
+          G: Graph<ID=usize, Weight=W, Value=T>,
+          W: Default {
+
+    pub fn add_edge<Q>(&mut self, u: &Q, v: &Q) -> GraphResult<EdgeId, K>
+        where K: Borrow<Q>, Q: Hash + Eq + ToOwned<Owned=K> + ?Sized {
+        self.add_edge_with(u, v, W::default())
+    }
+}
+
+///
+/// The inner graph's API, by `usize` ID. Nodes can only be added for IDs that already have a key,
+/// so that every node can be translated back
+impl<K, W, T, G> Graph for Interned<K, G>
+    where K: Eq + Hash + Clone,
+          G: Graph<ID=usize, Weight=W, Value=T> + Display {
+    type ID = usize;
+    type Weight = W;
+    type Value = T;
+
+    fn get_node(&self, id: &usize) -> Option<&Node<usize, T>> {
+        self.inner.get_node(id)
+    }
+
+    fn get_node_mut(&mut self, id: &usize) -> Option<&mut Node<usize, T>> {
+        self.inner.get_node_mut(id)
+    }
+
+    fn add_node_with(&mut self, id: usize, value: T) -> GraphResult<(), usize> {
+        if self.interner.get_key(id).is_none() {
+            return Err(IdDoesNotExist { id, operation: AddNode });
+        }
+        self.inner.add_node_with(id, value)
+    }
+
+    fn remove_node(&mut self, id: &usize) -> GraphResult<Node<usize, T>, usize> {
+        self.inner.remove_node(id)
+    }
+
+    fn contains_node(&self, id: &usize) -> bool {
+        self.inner.contains_node(id)
+    }
+
+    fn add_edge_with(&mut self, u: &usize, v: &usize, weight: W) -> GraphResult<EdgeId, usize> {
+        self.inner.add_edge_with(u, v, weight)
+    }
+
+    fn remove_edge(&mut self, u: &usize, v: &usize) -> GraphResult<W, usize> {
+        self.inner.remove_edge(u, v)
+    }
+
+    fn remove_edge_by_id(&mut self, edge: EdgeId) -> GraphResult<W, usize> {
+        self.inner.remove_edge_by_id(edge)
+    }
+
+    fn get_edge(&self, edge: EdgeId) -> Option<EdgeRef<'_, usize, W>> {
+        self.inner.get_edge(edge)
+    }
+
+    fn edge_id(&self, u: &usize, v: &usize) -> Option<EdgeId> {
+        self.inner.edge_id(u, v)
+    }
+
+    fn get_edges<'a>(&'a self, u: &usize, v: &usize) -> impl Iterator<Item=EdgeRef<'a, usize, W>> + use<'a, K, W, T, G> {
+        self.inner.get_edges(u, v)
+    }
+
+    fn contains_edge(&self, u: &usize, v: &usize) -> bool {
+        self.inner.contains_edge(u, v)
+    }
+
+    fn get_weight(&self, u: &usize, v: &usize) -> Option<&W> {
+        self.inner.get_weight(u, v)
+    }
+
+    fn update_weight<R, F>(&mut self, u: &usize, v: &usize, f: F) -> GraphResult<R, usize>
+        where F: FnOnce(&mut W) -> R {
+        self.inner.update_weight(u, v, f)
+    }
+
+    fn get_adjacent<'a>(&'a self, node: &usize) -> impl Iterator<Item=&'a usize> + use<'a, K, W, T, G> {
+        self.inner.get_adjacent(node)
+    }
+
+    fn get_incoming<'a>(&'a self, node: &usize) -> impl Iterator<Item=&'a usize> + use<'a, K, W, T, G> {
+        self.inner.get_incoming(node)
+    }
+
+    fn in_degree(&self, node: &usize) -> usize {
+        self.inner.in_degree(node)
+    }
+
+    fn out_degree(&self, node: &usize) -> usize {
+        self.inner.out_degree(node)
+    }
+
+    fn degree(&self, node: &usize) -> usize {
+        self.inner.degree(node)
+    }
+
+    fn nodes(&self) -> impl Iterator<Item=&Node<usize, T>> {
+        self.inner.nodes()
+    }
+
+    fn edges(&self) -> impl Iterator<Item=EdgeRef<'_, usize, W>> {
+        self.inner.edges()
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.inner.num_nodes()
+    }
+
+    fn num_edges(&self) -> usize {
+        self.inner.num_edges()
+    }
+
+    fn take_nodes(self) -> Vec<Node<usize, T>> {
+        self.inner.take_nodes()
+    }
+
+    ///
+    /// Removes every node and edge from the inner graph. Keys keep their IDs
+    fn clear(&mut self) {
+        self.inner.clear()
+    }
+}
+
+impl<K, W, T, G, Q> GraphLookup<Q> for Interned<K, G>
+    where K: Eq + Hash + Clone + Borrow<Q>,
+          Q: Hash + Eq + ?Sized,
+          G: Graph<ID=usize, Weight=W, Value=T> + Display {
+
+    fn lookup_node(&self, key: &Q) -> Option<&Node<usize, T>> {
+        self.inner.get_node(&self.id_of(key)?)
+    }
+
+    fn lookup_node_mut(&mut self, key: &Q) -> Option<&mut Node<usize, T>> {
+        let id = self.id_of(key)?;
+        self.inner.get_node_mut(&id)
+    }
+
+    fn lookup_weight(&self, u: &Q, v: &Q) -> Option<&W> {
+        let (u, v) = self.endpoints(u, v)?;
+        self.inner.get_weight(&u, &v)
+    }
+}
+
+impl<K, G> From<G> for Interned<K, G>
+    where K: Eq + Hash + Clone,
+          G: Graph<ID=usize> {
+    fn from(graph: G) -> Self {
+        Interned::new(graph)
+    }
+}
+
+impl<K, W, P> PathFinder<K, W> for Interned<K, P>
+    where K: Eq + Hash + Clone,
+          W: Num,
+          P: PathFinder<usize, W> {
+    fn find_path(&self, from: &K, to: &K) -> Option<(Vec<K>, W)> {
+        let (from, to) = self.endpoints(from, to)?;
+        let (path, weight) = self.inner.find_path(&from, &to)?;
+        let path = path.iter().map(|id| self.key(id).clone()).collect();
+        Some((path, weight))
+    }
+}
+
+impl<K, G> Display for Interned<K, G>
+    where K: Eq + Hash + Clone,
+          G: Display {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Interned{{ keys = {}, inner = {} }}", self.interner.len(), self.inner)
+    }
+}
+
+impl<K, G> Debug for Interned<K, G>
+    where K: Eq + Hash + Clone,
+          G: Display {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(self, f)
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::interned::Interned;
+    use crate::directed::Undirected;
+    use crate::pathing::{StatelessPathFinder, PathFinder};
+    use crate::algorithms::weakly_connected_components;
+    use crate::{HashGraph, Graph, GraphLookup, GraphError, Operation};
+
+    #[test]
+    fn string_keys() {
+        let mut graph: Interned<String, HashGraph<usize, usize, u32>> = Interned::new(HashGraph::new());
+        assert_eq!(graph.add_node_with("a".to_string(), 1).unwrap(), 0);
+        assert_eq!(graph.add_node_with("b".to_string(), 2).unwrap(), 1);
+        assert!(graph.add_node_with("a".to_string(), 3).is_err());
+        graph.add_edge_with("a", "b", 10).unwrap();
+
+        assert_eq!(graph.get("b"), Some(&2));
+        assert!(graph.contains_edge("a", "b"));
+        assert_eq!(graph.get_weight("a", "b"), Some(&10));
+        assert_eq!(graph.get_adjacent("a").collect::<Vec<_>>(), vec!["b"]);
+        assert_eq!(graph.get_incoming("b").collect::<Vec<_>>(), vec!["a"]);
+        assert_eq!(graph.add_edge_with("a", "c", 1),
+                   Err(GraphError::IdDoesNotExist { id: "c".to_string(), operation: Operation::AddEdge }));
+        assert_eq!(graph.remove_edge("b", "a"),
+                   Err(GraphError::EdgeDoesNotExist { u: "b".to_string(), v: "a".to_string(), operation: Operation::RemoveEdge }));
+
+        graph.remove_node("a").unwrap();
+        assert!(!graph.contains_node("a"));
+        graph.add_node_with("a".to_string(), 4).unwrap();
+        assert_eq!(graph.id_of("a"), Some(0), "Keys keep their ID");
+    }
+
+    #[test]
+    fn translated_paths() {
+        let mut graph = Interned::new(Undirected::from(HashGraph::<usize, ()>::new()));
+        let keys: Vec<u128> = vec![1 << 100, 7, 1 << 64, 42];
+        for key in &keys {
+            graph.add_node(*key).unwrap();
+        }
+        graph.add_edge(&keys[0], &keys[1]).unwrap();
+        graph.add_edge(&keys[1], &keys[2]).unwrap();
+        graph.add_edge(&keys[2], &keys[3]).unwrap();
+
+        let pathfinder = graph.map_inner(StatelessPathFinder::new);
+        let path: Option<(Vec<u128>, usize)> = pathfinder.find_path(&keys[3], &keys[0]);
+        let (path, weight) = path.expect("There is a path between the two keys");
+        assert_eq!(path, vec![keys[3], keys[2], keys[1], keys[0]]);
+        assert_eq!(weight, 3);
+        assert!(pathfinder.find_path(&keys[0], &5).is_none());
+    }
+
+    #[test]
+    fn failed_add_leaves_key_free() {
+        let mut graph: Interned<&str, HashGraph<usize, (), u32>> = Interned::new(HashGraph::new());
+        graph.inner.add_node_with(0, 0).unwrap();
+
+        assert_eq!(graph.add_node_with("a", 1), Err(GraphError::IdExists { id: "a", operation: Operation::AddNode }));
+        assert_eq!(graph.id_of("a"), None);
+        assert!(graph.interner().is_empty());
+    }
+
+    fn weight_between<G: GraphLookup<str, Weight=u32>>(graph: &G, u: &str, v: &str) -> Option<u32> {
+        graph.lookup_weight(u, v).copied()
+    }
+
+    #[test]
+    fn generic_algorithms() {
+        let mut graph: Interned<String, HashGraph<usize, u32>> = Interned::new(HashGraph::new());
+        for key in ["a", "b", "c", "d"] {
+            graph.add_node(key.to_string()).unwrap();
+        }
+        graph.add_edge_with("a", "b", 4).unwrap();
+        graph.add_edge_with("d", "c", 2).unwrap();
+
+        let mut components: Vec<Vec<&String>> = weakly_connected_components(&graph)
+            .into_iter()
+            .map(|component| {
+                let mut keys: Vec<&String> = component.into_iter().map(|id| graph.key_of(*id).unwrap()).collect();
+                keys.sort();
+                keys
+            })
+            .collect();
+        components.sort();
+        assert_eq!(components, vec![vec!["a", "b"], vec!["c", "d"]]);
+
+        assert_eq!(weight_between(&graph, "d", "c"), Some(2));
+        assert_eq!(weight_between(&graph, "c", "d"), None);
+        assert!(graph.has_edge("a", "b"));
+        assert_eq!(graph.lookup_node("b").map(|node| *node.get_id()), graph.id_of("b"));
+        assert_eq!(Graph::add_node_with(&mut graph, 9, ()),
+                   Err(GraphError::IdDoesNotExist { id: 9, operation: Operation::AddNode }));
+    }
+}
@@ -8,13 +8,17 @@ use std::iter::FromIterator;
 
 
 mod node;
+mod edge;
 mod graph;
 
 pub use crate::node::Node;
+pub use crate::edge::{EdgeId, EdgeRef};
 pub use crate::graph::*;
 
 pub mod directed;
 pub mod pathing;
+pub mod interned;
+pub mod algorithms;
 
 ///
 /// The base structure of the HashGraph
@@ -69,6 +73,21 @@ mod test {
         assert_eq!(g[(1, 2)], 10.0)
     }
 
+    #[test]
+    fn mutate_weight() {
+        let mut g: HashGraph<_, f64> = HashGraph::new();
+
+        g.add_nodes_with(0..3, ()).unwrap();
+        g.add_edge_with(&1, &2, 10.0).unwrap();
+        *g.get_weight_mut(&1, &2).unwrap() += 1.0;
+        assert_eq!(g[(1, 2)], 11.0);
+        g[(1, 2)] = 12.0;
+        assert_eq!(g.set_weight(&1, &2, 13.0).unwrap(), 12.0);
+        assert_eq!(g.get_weight(&1, &2), Some(&13.0));
+        assert!(g.set_weight(&0, &1, 1.0).is_err());
+        assert!(g.get_weight_mut(&2, &1).is_none());
+    }
+
     #[test]
     fn change_value() {
         let mut g: HashGraph<i32, f64, i32> = HashGraph::new();
@@ -86,7 +105,7 @@ mod test {
         g.add_edge(&0, &1).unwrap();
         g.add_edge(&0, &3).unwrap();
         g.add_edge(&0, &7).unwrap();
-        let mut v = g.get_adjacent(&0);
+        let mut v = g.get_adjacent_vec(&0);
         v.sort();
 
         assert_eq!(v, vec![&1, &3, &7]);
@@ -106,19 +125,20 @@ mod test {
         assert_eq!(g_prime.get_weight(&3, &5), g.get_weight(&3, &5));
     }
 
-    /*
     #[test]
-    fn test_sub_HashGraphs() {
+    fn test_sub_graphs() {
         let mut g: HashGraph<usize, (), ()> = HashGraph::new();
-        g.add_nodes(0..6).unwrap();
+        g.add_nodes(0..7).unwrap();
         g.add_edge(&0, &1).unwrap();
         g.add_edge(&1, &2).unwrap();
         g.add_edge(&2, &3).unwrap();
         g.add_edge(&4,&5).unwrap();
 
-        let sub_HashGraphs = g.get_sub_HashGraphs();
-        assert_eq!(sub_HashGraphs.len(), 3);
-    }
+        let sub_graphs = g.get_sub_graphs();
+        assert_eq!(sub_graphs.len(), 3);
 
-     */
+        let mut owned = g.into_sub_graphs();
+        owned.sort_by_key(|sub_graph| sub_graph.num_nodes());
+        assert_eq!(owned.iter().map(|sub_graph| sub_graph.num_edges()).collect::<Vec<_>>(), vec![0, 1, 3]);
+    }
 }
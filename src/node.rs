@@ -9,7 +9,7 @@ pub struct Node<ID = usize, T = ()>
 }
 
 impl<ID, T> Display for Node<ID, T> where
-    ID: PartialEq + Copy + Display,
+    ID: Display,
     T : Display {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(f, "{{{}:{}}}", self.id, self.value)
@@ -17,7 +17,7 @@ impl<ID, T> Display for Node<ID, T> where
 }
 
 impl<ID, T> Debug for Node<ID, T> where
-    ID: PartialEq + Copy + Debug,
+    ID: Debug,
     T : Debug {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(f, "{{{:?}:{:?}}}", self.id, self.value)
@@ -26,42 +26,39 @@ impl<ID, T> Debug for Node<ID, T> where
 
 impl<ID, T> Clone for Node<ID, T>
     where
-        ID: PartialEq + Copy,
+        ID: Clone,
         T: Clone,
 {
     fn clone(&self) -> Self {
         Self {
-            id: self.id,
+            id: self.id.clone(),
             value: self.value.clone(),
         }
     }
 }
 
 impl <ID,T> Hash for Node<ID, T>
-    where ID : Hash + PartialEq + Copy {
+    where ID : Hash {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.id.hash(state);
     }
 }
 
 impl<ID, T> PartialEq for Node<ID, T>
-    where ID : PartialEq + Copy {
+    where ID : PartialEq {
     fn eq(&self, other: &Self) -> bool {
         self.id.eq(&other.id)
     }
 }
 
 impl<ID, T> Eq for Node<ID, T>
-    where ID : PartialEq + Copy {
+    where ID : Eq {
 
 }
 
 
 
-impl<ID, T> Deref for Node<ID, T>
-    where
-        ID: PartialEq + Copy,
-{
+impl<ID, T> Deref for Node<ID, T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
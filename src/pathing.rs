@@ -2,12 +2,19 @@ use crate::Graph;
 use std::ops::{Add, Sub};
 use num_traits::Num;
 use map_vec::{Map, Set};
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
 use std::cmp::Ordering;
 use std::cmp::Ordering::{Equal, Greater, Less};
-use std::thread::current;
 
 pub mod spatial;
+mod tree;
+mod bellman_ford;
+mod all_pairs;
+
+pub use tree::ShortestPathTree;
+pub use bellman_ford::{BellmanFordPathFinder, NegativeCycleError};
+pub use all_pairs::{DistanceMatrix, floyd_warshall, johnson};
 
 pub trait PathFinder<ID : PartialEq + Clone, W : Num> {
 
@@ -16,6 +23,7 @@ pub trait PathFinder<ID : PartialEq + Clone, W : Num> {
 
 pub trait WeightRepr<W> where
 W : PartialOrd + Add + Sub {
+    #[allow(clippy::wrong_self_convention)]
     fn into_weight(&self) -> W;
 }
 
@@ -34,6 +42,79 @@ impl<ID: Eq, W, T, G: Graph<ID=ID, Weight=W, Value=T>> StatelessPathFinder<ID, W
     }
 }
 
+impl<ID: Eq + Hash + Clone, W, T, G: Graph<ID=ID, Weight=W, Value=T>> StatelessPathFinder<ID, W, T, G> {
+
+    ///
+    /// Finds the shortest path from `from` to every node it can reach
+    pub fn shortest_paths<WI>(&self, from: &ID) -> ShortestPathTree<ID, WI>
+        where W : WeightRepr<WI>,
+              WI : Num + PartialOrd + Add + Sub + Clone {
+        self.grow_tree(from, None, |_, _| false)
+    }
+
+    ///
+    /// Finds the shortest path from `from` to every node it can reach for at most `limit`
+    pub fn shortest_paths_within<WI>(&self, from: &ID, limit: WI) -> ShortestPathTree<ID, WI>
+        where W : WeightRepr<WI>,
+              WI : Num + PartialOrd + Add + Sub + Clone {
+        self.grow_tree(from, Some(&limit), |_, _| false)
+    }
+
+    ///
+    /// Finds shortest paths from `from` in order of distance, until `stop` returns true for a node
+    /// and its distance. That node is the last one added to the tree
+    pub fn shortest_paths_until<WI, F>(&self, from: &ID, stop: F) -> ShortestPathTree<ID, WI>
+        where W : WeightRepr<WI>,
+              WI : Num + PartialOrd + Add + Sub + Clone,
+              F : FnMut(&ID, &WI) -> bool {
+        self.grow_tree(from, None, stop)
+    }
+
+    fn grow_tree<WI, F>(&self, from: &ID, limit: Option<&WI>, mut stop: F) -> ShortestPathTree<ID, WI>
+        where W : WeightRepr<WI>,
+              WI : Num + PartialOrd + Add + Sub + Clone,
+              F : FnMut(&ID, &WI) -> bool {
+        let mut reached = HashMap::new();
+        let from = match self.0.get_node(from) {
+            None => return ShortestPathTree::new(from.clone(), reached),
+            Some(node) => node.get_id(),
+        };
+
+        let mut tentative: HashMap<&ID, (WI, Option<&ID>)> = HashMap::new();
+        tentative.insert(from, (WI::zero(), None));
+        let mut queue = BinaryHeap::new();
+        queue.push(IdWrapper(from, Some(WI::zero())));
+
+        while let Some(IdWrapper(current, _)) = queue.pop() {
+            if reached.contains_key(current) {
+                continue;
+            }
+            let (current_distance, previous) = tentative.remove(current).expect("Queued nodes have a distance");
+            if limit.is_some_and(|limit| current_distance > *limit) {
+                break;
+            }
+            reached.insert(current.clone(), (current_distance.clone(), previous.cloned()));
+            if stop(current, &current_distance) {
+                break;
+            }
+
+            for adj in self.0.get_adjacent(current) {
+                if reached.contains_key(adj) {
+                    continue;
+                }
+                let weight = cheapest_weight(&self.0, current, adj).expect("Adjacent nodes have an edge");
+                let new_distance = current_distance.clone() + weight;
+                if tentative.get(adj).is_none_or(|(old, _)| new_distance < *old) {
+                    tentative.insert(adj, (new_distance.clone(), Some(current)));
+                    queue.push(IdWrapper(adj, Some(new_distance)));
+                }
+            }
+        }
+
+        ShortestPathTree::new(from.clone(), reached)
+    }
+}
+
 /*
 impl<ID: Eq, W: PartialOrd + Add + Sub , T, G: Graph<ID=ID, Weight=W, Value=T>> PathFinder<ID, W> for StatelessPathFinder<ID, W, T, G> {
     fn find_path(&self, from: &ID, to: &ID) -> Option<(Vec<&ID>, W)> {
@@ -49,9 +130,23 @@ impl <T : Num+ PartialOrd + Add + Sub + Clone> WeightRepr<T> for T {
     }
 }
 
-struct IdWrapper<'a, ID : Eq, W : PartialOrd>(&'a ID, Option<W>);
+///
+/// Gets the smallest weight of all of the `u -> v` edges, which is only ambiguous in multigraphs
+pub(crate) fn cheapest_weight<WI, G>(graph: &G, u: &G::ID, v: &G::ID) -> Option<WI>
+    where G: Graph,
+          G::Weight: WeightRepr<WI>,
+          WI: PartialOrd + Add + Sub {
+    graph.get_weights(u, v)
+        .map(|weight| weight.into_weight())
+        .fold(None, |cheapest, weight| match cheapest {
+            Some(cheapest) if cheapest <= weight => Some(cheapest),
+            _ => Some(weight),
+        })
+}
+
+pub(crate) struct IdWrapper<'a, ID : Eq, W : PartialOrd>(&'a ID, Option<W>);
 
-impl<'a, 'b, ID : Eq, W : PartialOrd> PartialEq for IdWrapper<'a, ID, W> {
+impl<'a, ID : Eq, W : PartialOrd> PartialEq for IdWrapper<'a, ID, W> {
     fn eq(&self, other: &Self) -> bool {
         match (&self.1, &other.1) {
             (Some(this), Some(other)) => {
@@ -70,32 +165,32 @@ impl<'a, 'b, ID : Eq, W : PartialOrd> PartialEq for IdWrapper<'a, ID, W> {
     }
 }
 
-impl<'a, 'b, ID : Eq, W : PartialOrd> PartialOrd for IdWrapper<'a, ID, W> {
+impl<'a, ID : Eq, W : PartialOrd> PartialOrd for IdWrapper<'a, ID, W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, ID : Eq, W : PartialOrd> Ord for IdWrapper<'a, ID, W> {
+    fn cmp(&self, other: &Self) -> Ordering {
         match (&self.1, &other.1) {
             (Some(this), Some(other)) => {
-                other.partial_cmp(this)
+                other.partial_cmp(this).unwrap()
             },
             (Some(_), None) => {
-                Some(Greater)
+                Greater
             },
             (None, Some(_)) => {
-                Some(Less)
+                Less
             },
             (None, None) => {
-                Some(Equal)
+                Equal
             }
         }
     }
 }
 
-impl<'a, 'b, ID : Eq, W : PartialOrd> Ord for IdWrapper<'a, ID, W> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
-    }
-}
-
-impl<'a, 'b, ID : Eq, W : PartialOrd> Eq for IdWrapper<'a, ID, W> {
+impl<'a, ID : Eq, W : PartialOrd> Eq for IdWrapper<'a, ID, W> {
 
 }
 
@@ -111,13 +206,8 @@ impl<ID: Eq + Clone, WI : Num + PartialOrd + Add + Sub + Clone, W : WeightRepr<W
         let mut distance = Map::new();
         distance.insert(from, WI::zero());
 
-        let long_life_time_borrow = self.0.nodes().iter().map(|node| {
-            (node.get_id(), node.get_id())
-        }).collect::<Map<&ID, &ID>>();
-
-
         let mut queue = BinaryHeap::new();
-        queue.push(IdWrapper(from, distance.get(from).map(|w| w.clone())));
+        queue.push(IdWrapper(from, distance.get(from).cloned()));
 
         loop {
             let current =
@@ -148,15 +238,14 @@ impl<ID: Eq + Clone, WI : Num + PartialOrd + Add + Sub + Clone, W : WeightRepr<W
 
                 if !visited.contains(adj) {
 
-                    let path_length = match self.0.get_weight(current, adj) {
+                    let path_length = match cheapest_weight(&self.0, current, adj) {
                         None => { panic!("This souldn't happen, weight should always exist")},
                         Some(weight) => {
                             weight
                         },
-                    }.into_weight();
+                    };
 
 
-                    let fixed = long_life_time_borrow[adj];
                     let new_distance: WI = path_length + current_distance.clone();
 
                      match distance.get(adj) {
@@ -174,7 +263,7 @@ impl<ID: Eq + Clone, WI : Num + PartialOrd + Add + Sub + Clone, W : WeightRepr<W
                         },
                     }
 
-                    queue.push(IdWrapper(fixed, Some(new_distance)));
+                    queue.push(IdWrapper(adj, Some(new_distance)));
 
 
                 }
@@ -195,7 +284,7 @@ impl<ID: Eq + Clone, WI : Num + PartialOrd + Add + Sub + Clone, W : WeightRepr<W
                 match prev {
                     None => {},
                     Some(prev) => {
-                        let weight = self.0.get_weight(prev, current).unwrap().into_weight();
+                        let weight = cheapest_weight(&self.0, prev, current).unwrap();
                         total_weight = total_weight + weight;
                     },
                 }
@@ -0,0 +1,271 @@
+use crate::Graph;
+use crate::algorithms::IndexedGraph;
+use crate::pathing::{PathFinder, WeightRepr, IdWrapper, NegativeCycleError};
+use crate::pathing::bellman_ford::{weighted_edges, relax_from};
+use num_traits::Num;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+///
+/// The length of the shortest path between every pair of nodes, along with what's needed to
+/// rebuild the paths themselves
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceMatrix<ID : Eq + Hash, W> {
+    ids: Vec<ID>,
+    index: HashMap<ID, usize>,
+    /// Row `u` holds the distances from `u`
+    distance: Vec<Option<W>>,
+    /// Row `u` holds the node before each other node on the shortest path from `u`
+    previous: Vec<Option<usize>>,
+}
+
+impl<ID : Eq + Hash + Clone, W> DistanceMatrix<ID, W> {
+
+    fn new<G: Graph<ID=ID>>(nodes: &IndexedGraph<'_, G>, distance: Vec<Option<W>>, previous: Vec<Option<usize>>) -> Self {
+        let ids: Vec<ID> = nodes.ids.iter().map(|id| (*id).clone()).collect();
+        Self {
+            index: ids.iter().cloned().enumerate().map(|(i, id)| (id, i)).collect(),
+            ids,
+            distance,
+            previous,
+        }
+    }
+
+    ///
+    /// The nodes of the graph, in the order of the matrix's rows and columns
+    pub fn ids(&self) -> &[ID] {
+        &self.ids
+    }
+
+    fn cell(&self, from: &ID, to: &ID) -> Option<(usize, usize)> {
+        Some((*self.index.get(from)?, *self.index.get(to)?))
+    }
+
+    ///
+    /// The length of the shortest path from `from` to `to`, if there is one
+    pub fn distance(&self, from: &ID, to: &ID) -> Option<&W> {
+        let (from, to) = self.cell(from, to)?;
+        self.distance[from * self.ids.len() + to].as_ref()
+    }
+
+    ///
+    /// The shortest path from `from` to `to`, including both ends
+    pub fn path(&self, from: &ID, to: &ID) -> Option<Vec<ID>> {
+        let (from, to) = self.cell(from, to)?;
+        let row = from * self.ids.len();
+        self.distance[row + to].as_ref()?;
+
+        let mut path = vec![self.ids[to].clone()];
+        let mut node = to;
+        while node != from {
+            node = self.previous[row + node].expect("Nodes on a path have a predecessor");
+            path.push(self.ids[node].clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<ID : Eq + Hash + Clone, W : Num + Clone> PathFinder<ID, W> for DistanceMatrix<ID, W> {
+
+    fn find_path(&self, from: &ID, to: &ID) -> Option<(Vec<ID>, W)> {
+        Some((self.path(from, to)?, self.distance(from, to)?.clone()))
+    }
+}
+
+///
+/// Finds the shortest paths between every pair of nodes with the Floyd–Warshall algorithm, which
+/// handles negative weights and takes time proportional to the cube of the number of nodes. Best
+/// for dense graphs
+pub fn floyd_warshall<G, WI>(graph: &G) -> Result<DistanceMatrix<G::ID, WI>, NegativeCycleError<G::ID>>
+    where G : Graph,
+          G::ID : Hash + Clone,
+          G::Weight : WeightRepr<WI>,
+          WI : Num + PartialOrd + Add + Sub + Clone {
+    let nodes = IndexedGraph::new(graph);
+    let count = nodes.len();
+    let edges = weighted_edges(graph, &nodes);
+    let mut distance: Vec<Option<WI>> = vec![None; count * count];
+    let mut previous = vec![None; count * count];
+    for node in 0..count {
+        distance[node * count + node] = Some(WI::zero());
+    }
+    for (u, v, weight) in &edges {
+        let cell = u * count + v;
+        if distance[cell].as_ref().is_none_or(|old| weight < old) {
+            distance[cell] = Some(weight.clone());
+            previous[cell] = Some(*u);
+        }
+    }
+
+    let zero = WI::zero();
+    // Stop at the first negative cycle, since going around it again could overflow the weights
+    'passes: for k in 0..count {
+        for i in 0..count {
+            let to_k = match &distance[i * count + k] {
+                None => continue,
+                Some(to_k) => to_k.clone(),
+            };
+            for j in 0..count {
+                let through_k = match &distance[k * count + j] {
+                    None => continue,
+                    Some(from_k) => to_k.clone() + from_k.clone(),
+                };
+                if distance[i * count + j].as_ref().is_none_or(|old| through_k < *old) {
+                    let negative_cycle = i == j && through_k < zero;
+                    distance[i * count + j] = Some(through_k);
+                    previous[i * count + j] = previous[k * count + j];
+                    if negative_cycle {
+                        break 'passes;
+                    }
+                }
+            }
+        }
+    }
+
+    if (0..count).any(|node| distance[node * count + node].as_ref().is_some_and(|distance| *distance < zero)) {
+        // The predecessors are unreliable once there's a negative cycle, so find it the slow way
+        let sources: Vec<usize> = (0..count).collect();
+        if let Err(cycle) = relax_from(count, &edges, &sources) {
+            return Err(NegativeCycleError::from_indices(&nodes, cycle));
+        }
+        unreachable!("A node can reach itself for less than nothing");
+    }
+
+    Ok(DistanceMatrix::new(&nodes, distance, previous))
+}
+
+///
+/// Finds the shortest paths between every pair of nodes with Johnson's algorithm, which reweights
+/// the edges so that none are negative and then runs Dijkstra's algorithm from every node. Best
+/// for sparse graphs
+pub fn johnson<G, WI>(graph: &G) -> Result<DistanceMatrix<G::ID, WI>, NegativeCycleError<G::ID>>
+    where G : Graph,
+          G::ID : Hash + Clone,
+          G::Weight : WeightRepr<WI>,
+          WI : Num + PartialOrd + Add + Sub + Clone {
+    let nodes = IndexedGraph::new(graph);
+    let count = nodes.len();
+    let edges = weighted_edges(graph, &nodes);
+
+    // Starting every node at zero is the same as adding a new node with a free edge to each of them
+    let sources: Vec<usize> = (0..count).collect();
+    let (potential, _) = relax_from(count, &edges, &sources)
+        .map_err(|cycle| NegativeCycleError::from_indices(&nodes, cycle))?;
+    let potential: Vec<WI> = potential.into_iter().map(|potential| potential.expect("Every node is a source")).collect();
+
+    let mut leaving = vec![vec![]; count];
+    for (u, v, weight) in edges {
+        leaving[u].push((v, weight + potential[u].clone() - potential[v].clone()));
+    }
+
+    let mut distance: Vec<Option<WI>> = vec![None; count * count];
+    let mut previous = vec![None; count * count];
+    for source in &sources {
+        let row = source * count;
+        let mut done = vec![false; count];
+        // The heap holds dense indices, so there's no need to go back through the IDs
+        let mut queue = BinaryHeap::new();
+        distance[row + source] = Some(WI::zero());
+        queue.push(IdWrapper(source, Some(WI::zero())));
+
+        while let Some(IdWrapper(&u, _)) = queue.pop() {
+            if done[u] {
+                continue;
+            }
+            done[u] = true;
+            let to_u = distance[row + u].clone().expect("Queued nodes have a distance");
+            for (v, weight) in &leaving[u] {
+                let through_u = to_u.clone() + weight.clone();
+                if !done[*v] && distance[row + v].as_ref().is_none_or(|old| through_u < *old) {
+                    distance[row + v] = Some(through_u.clone());
+                    previous[row + v] = Some(u);
+                    queue.push(IdWrapper(v, Some(through_u)));
+                }
+            }
+        }
+
+        for target in 0..count {
+            if let Some(reweighted) = distance[row + target].take() {
+                distance[row + target] = Some(reweighted - potential[*source].clone() + potential[target].clone());
+            }
+        }
+    }
+
+    Ok(DistanceMatrix::new(&nodes, distance, previous))
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::pathing::{floyd_warshall, johnson, PathFinder};
+    use crate::{HashGraph, Graph};
+
+    fn sites() -> HashGraph<&'static str, i32, ()> {
+        let mut g = HashGraph::new();
+        for id in ["depot", "north", "east", "south", "west", "island"] {
+            g.add_node_with(id, ()).unwrap();
+        }
+        for (u, v, w) in [("depot", "north", 3), ("depot", "east", 8), ("north", "east", 4), ("east", "south", -2),
+                          ("south", "west", 2), ("west", "north", 1), ("north", "depot", 5)] {
+            g.add_edge_with(&u, &v, w).unwrap();
+        }
+        g
+    }
+
+    #[test]
+    fn both_algorithms_agree() {
+        let g = sites();
+        let dense = floyd_warshall::<_, i32>(&g).unwrap();
+        let sparse = johnson::<_, i32>(&g).unwrap();
+
+        assert_eq!(dense.ids().len(), 6);
+        for u in dense.ids() {
+            for v in dense.ids() {
+                assert_eq!(dense.distance(u, v), sparse.distance(u, v), "{} -> {}", u, v);
+                assert_eq!(dense.path(u, v).is_some(), sparse.path(u, v).is_some());
+            }
+        }
+
+        assert_eq!(dense.distance(&"depot", &"west"), Some(&7));
+        assert_eq!(sparse.path(&"depot", &"west"), Some(vec!["depot", "north", "east", "south", "west"]));
+        assert_eq!(dense.find_path(&"west", &"depot"), Some((vec!["west", "north", "depot"], 6)));
+        assert_eq!(dense.distance(&"south", &"south"), Some(&0));
+        assert_eq!(sparse.path(&"depot", &"island"), None);
+        assert_eq!(dense.distance(&"depot", &"nowhere"), None);
+    }
+
+    #[test]
+    fn negative_cycles() {
+        let mut g = sites();
+        g.add_edge_with(&"south", &"north", -3).unwrap();
+
+        let cycle: Vec<&str> = floyd_warshall::<_, i32>(&g).unwrap_err().into_cycle();
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(johnson::<_, i32>(&g).unwrap_err().cycle().len(), 3);
+
+        let mut looped: HashGraph<u32, i32, ()> = HashGraph::new();
+        looped.add_node_with(0, ()).unwrap();
+        looped.add_edge_with(&0, &0, -1).unwrap();
+        assert_eq!(floyd_warshall::<_, i32>(&looped).unwrap_err().into_cycle(), vec![0]);
+    }
+
+    #[test]
+    fn negative_cycle_without_overflow() {
+        // Every pair of nodes is a negative cycle, and each pass past one would make the
+        // distances roughly twice as negative
+        let mut g: HashGraph<u32, i16, ()> = HashGraph::new();
+        for node in 0..11 {
+            g.add_node_with(node, ()).unwrap();
+        }
+        for u in 0..11 {
+            for v in (0..11).filter(|v| *v != u) {
+                g.add_edge_with(&u, &v, -1).unwrap();
+            }
+        }
+
+        assert!(floyd_warshall::<_, i16>(&g).is_err());
+        assert!(johnson::<_, i16>(&g).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use crate::Graph;
+use crate::algorithms::IndexedGraph;
+use crate::pathing::{PathFinder, WeightRepr, ShortestPathTree, cheapest_weight};
+use num_traits::Num;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter, Result};
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+///
+/// The reason shortest paths don't exist: a cycle whose weights add up to less than zero, so going
+/// around it again always makes a path shorter. Each ID has an edge to the next and the last has an
+/// edge back to the first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeCycleError<ID> {
+    cycle: Vec<ID>,
+}
+
+impl<ID> NegativeCycleError<ID> {
+
+    pub fn cycle(&self) -> &[ID] {
+        &self.cycle
+    }
+
+    pub fn into_cycle(self) -> Vec<ID> {
+        self.cycle
+    }
+}
+
+impl<ID : Clone> NegativeCycleError<ID> {
+
+    pub(crate) fn from_indices<G: Graph<ID=ID>>(nodes: &IndexedGraph<'_, G>, cycle: Vec<usize>) -> Self {
+        NegativeCycleError { cycle: cycle.into_iter().map(|node| nodes.id(node).clone()).collect() }
+    }
+}
+
+impl<ID: Debug> Display for NegativeCycleError<ID> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "The graph has a negative cycle: ")?;
+        for id in &self.cycle {
+            write!(f, "{:?} -> ", id)?;
+        }
+        write!(f, "{:?}", self.cycle[0])
+    }
+}
+
+impl<ID: Debug> std::error::Error for NegativeCycleError<ID> {}
+
+///
+/// The graph's edges by node index, keeping only the cheapest of any parallel edges
+pub(crate) fn weighted_edges<G, WI>(graph: &G, nodes: &IndexedGraph<'_, G>) -> Vec<(usize, usize, WI)>
+    where G : Graph,
+          G::Weight : WeightRepr<WI>,
+          WI : PartialOrd + Add + Sub {
+    (0..nodes.len())
+        .flat_map(|u| nodes.successors[u].iter().map(move |&v| (u, v)))
+        .map(|(u, v)| {
+            let weight = cheapest_weight(graph, nodes.id(u), nodes.id(v)).expect("Adjacent nodes have an edge");
+            (u, v, weight)
+        })
+        .collect()
+}
+
+///
+/// Each node's distance from the nearest source and the node before it on that path
+pub(crate) type Relaxed<WI> = (Vec<Option<WI>>, Vec<Option<usize>>);
+
+///
+/// Bellman-Ford over `count` nodes, starting every source at zero. Gives each node's distance and
+/// predecessor, or the nodes of a negative cycle reachable from the sources
+pub(crate) fn relax_from<WI>(count: usize, edges: &[(usize, usize, WI)], sources: &[usize]) -> std::result::Result<Relaxed<WI>, Vec<usize>>
+    where WI : Num + PartialOrd + Clone {
+    let mut distance: Vec<Option<WI>> = vec![None; count];
+    let mut previous = vec![None; count];
+    for &source in sources {
+        distance[source] = Some(WI::zero());
+    }
+
+    // After as many rounds as there are nodes, only a negative cycle can still shorten a path
+    let mut relaxed = None;
+    for _ in 0..count {
+        relaxed = None;
+        for (u, v, weight) in edges {
+            let through_u = match &distance[*u] {
+                None => continue,
+                Some(distance) => distance.clone() + weight.clone(),
+            };
+            if distance[*v].as_ref().is_none_or(|old| through_u < *old) {
+                distance[*v] = Some(through_u);
+                previous[*v] = Some(*u);
+                relaxed = Some(*v);
+            }
+        }
+        if relaxed.is_none() {
+            break;
+        }
+    }
+
+    match relaxed {
+        None => Ok((distance, previous)),
+        Some(mut node) => {
+            // Walking back far enough from a node that is still being relaxed is sure to end up on the cycle
+            for _ in 0..count {
+                node = previous[node].expect("Relaxed nodes have a predecessor");
+            }
+            let mut cycle = vec![node];
+            let mut next = previous[node].expect("Nodes on the cycle have a predecessor");
+            while next != node {
+                cycle.push(next);
+                next = previous[next].expect("Nodes on the cycle have a predecessor");
+            }
+            cycle.reverse();
+            Err(cycle)
+        },
+    }
+}
+
+///
+/// Finds shortest paths with the Bellman-Ford algorithm, which unlike [`StatelessPathFinder`](crate::pathing::StatelessPathFinder)
+/// handles negative weights. Takes time proportional to the number of nodes times the number of edges
+pub struct BellmanFordPathFinder<ID : Eq, W, T, G : Graph<ID=ID,Weight=W,Value=T>>(G);
+
+impl<ID : Eq, W, T, G : Graph<ID=ID,Weight=W,Value=T>> BellmanFordPathFinder<ID, W, T, G> {
+
+    pub fn new(graph: G) -> Self {
+        Self(graph)
+    }
+
+    pub fn get_graph(&self) -> &G {
+        &self.0
+    }
+}
+
+impl<ID : Eq + Hash + Clone, W, T, G : Graph<ID=ID,Weight=W,Value=T>> BellmanFordPathFinder<ID, W, T, G> {
+
+    ///
+    /// Finds the shortest path from `from` to every node it can reach, or a negative cycle that can
+    /// be reached from `from`. Negative cycles elsewhere in the graph don't matter
+    pub fn shortest_paths<WI>(&self, from: &ID) -> std::result::Result<ShortestPathTree<ID, WI>, NegativeCycleError<ID>>
+        where W : WeightRepr<WI>,
+              WI : Num + PartialOrd + Add + Sub + Clone {
+        let nodes = IndexedGraph::new(&self.0);
+        let source = match nodes.index.get(from) {
+            None => return Ok(ShortestPathTree::new(from.clone(), HashMap::new())),
+            Some(&source) => source,
+        };
+        let edges = weighted_edges(&self.0, &nodes);
+        let (distance, previous) = relax_from(nodes.len(), &edges, &[source])
+            .map_err(|cycle| NegativeCycleError::from_indices(&nodes, cycle))?;
+
+        let reached = distance.into_iter()
+            .enumerate()
+            .filter_map(|(node, distance)| {
+                distance.map(|distance| (nodes.id(node).clone(), (distance, previous[node].map(|previous| nodes.id(previous).clone()))))
+            })
+            .collect();
+        Ok(ShortestPathTree::new(from.clone(), reached))
+    }
+
+    ///
+    /// Finds the shortest path between two nodes, or the negative cycle that means there isn't one
+    pub fn try_find_path<WI>(&self, from: &ID, to: &ID) -> std::result::Result<Option<(Vec<ID>, WI)>, NegativeCycleError<ID>>
+        where W : WeightRepr<WI>,
+              WI : Num + PartialOrd + Add + Sub + Clone {
+        let tree = self.shortest_paths(from)?;
+        Ok(tree.path_to(to).map(|path| (path, tree.distance(to).cloned().expect("The path was found"))))
+    }
+}
+
+///
+/// Gives `None` when there is no path, including when a negative cycle can be reached from `from`.
+/// Use [`BellmanFordPathFinder::try_find_path`] to tell these apart
+impl<ID : Eq + Hash + Clone, WI : Num + PartialOrd + Add + Sub + Clone, W : WeightRepr<WI>, T, G: Graph<ID=ID, Weight=W, Value=T>> PathFinder<ID, WI> for BellmanFordPathFinder<ID, W, T, G> {
+
+    fn find_path(&self, from: &ID, to: &ID) -> Option<(Vec<ID>, WI)> {
+        self.try_find_path(from, to).ok().flatten()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::pathing::{BellmanFordPathFinder, PathFinder};
+    use crate::{HashGraph, Graph};
+
+    #[test]
+    fn negative_weights() {
+        let mut g = HashGraph::<char, i32, ()>::new();
+        for id in "sabcd".chars() {
+            g.add_node_with(id, ()).unwrap();
+        }
+        for (u, v, w) in [('s', 'a', 4), ('s', 'b', 2), ('a', 'c', -3), ('b', 'a', 1), ('c', 'd', 2), ('b', 'd', 6)] {
+            g.add_edge_with(&u, &v, w).unwrap();
+        }
+
+        let finder = BellmanFordPathFinder::new(g);
+        assert_eq!(finder.find_path(&'s', &'d'), Some((vec!['s', 'b', 'a', 'c', 'd'], 2)));
+
+        let tree = finder.shortest_paths(&'s').unwrap();
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.distance(&'c'), Some(&0));
+        assert_eq!(tree.predecessor(&'a'), Some(&'b'));
+        assert_eq!(tree.predecessor(&'s'), None);
+
+        let from_d = finder.shortest_paths::<i32>(&'d').unwrap();
+        assert_eq!(from_d.distances().count(), 1);
+        assert_eq!(finder.try_find_path::<i32>(&'d', &'s'), Ok(None));
+    }
+
+    #[test]
+    fn negative_cycles() {
+        let mut g = HashGraph::<u32, i32, ()>::new();
+        g.add_nodes(0..5).unwrap();
+        for (u, v, w) in [(0, 1, 1), (1, 2, 2), (2, 3, -2), (3, 1, -1), (3, 4, 1)] {
+            g.add_edge_with(&u, &v, w).unwrap();
+        }
+        g.add_node_with(5, ()).unwrap();
+
+        let finder = BellmanFordPathFinder::new(g);
+        let error = finder.try_find_path::<i32>(&0, &4).unwrap_err();
+        let cycle = error.cycle();
+        assert_eq!(cycle.len(), 3);
+        let total: i32 = (0..cycle.len())
+            .map(|i| *finder.get_graph().get_weight(&cycle[i], &cycle[(i + 1) % cycle.len()]).unwrap())
+            .sum();
+        assert!(total < 0);
+        assert!(error.to_string().starts_with("The graph has a negative cycle: "));
+        assert_eq!(finder.find_path(&0, &4), None::<(Vec<u32>, i32)>);
+
+        assert!(finder.shortest_paths::<i32>(&4).is_ok(), "The cycle can't be reached from 4");
+    }
+}
@@ -2,7 +2,7 @@ use crate::{Node, Graph};
 use std::ops::{Deref, Sub, Add};
 
 mod astar;
-pub use astar::HeuristicPathFinder;
+pub use astar::{HeuristicPathFinder, ValueHeuristic};
 use num_traits::{Num, Float, PrimInt, ToPrimitive};
 use num_traits::real::Real;
 use crate::directed::Undirected;
@@ -13,9 +13,9 @@ pub trait  Heuristic<ID : Eq, T, W> {
 }
 
 
-impl <F, W, ID : Eq> Heuristic<ID, (), W> for F where
-    F : Fn(&Node<ID, ()>, &Node<ID, ()>) -> W {
-    fn get_heuristic_value(&self, node1: &Node<ID, ()>, node2: &Node<ID, ()>) -> W {
+impl <F, W, ID : Eq, T> Heuristic<ID, T, W> for F where
+    F : Fn(&Node<ID, T>, &Node<ID, T>) -> W {
+    fn get_heuristic_value(&self, node1: &Node<ID, T>, node2: &Node<ID, T>) -> W {
         self(node1, node2)
     }
 }
@@ -1,4 +1,190 @@
-use crate::Graph;
+use crate::{Graph, Node};
+use crate::pathing::{PathFinder, WeightRepr, IdWrapper, cheapest_weight};
 use crate::pathing::spatial::Heuristic;
+use num_traits::Num;
+use map_vec::{Map, Set};
+use std::collections::BinaryHeap;
+use std::ops::{Add, Sub};
 
-pub struct HeuristicPathFinder<ID : Eq, W, T : Heuristic<ID, T, W>, G : Graph<ID=ID,Weight=W,Value=T>>(G);
\ No newline at end of file
+///
+/// Uses the values of the nodes themselves as the heuristic, asking the goal's value to estimate
+/// the distance from each node to the goal
+pub struct ValueHeuristic;
+
+impl<ID : Eq, T : Heuristic<ID, T, W>, W> Heuristic<ID, T, W> for ValueHeuristic {
+    fn get_heuristic_value(&self, node1: &Node<ID, T>, node2: &Node<ID, T>) -> W {
+        node2.get_value().get_heuristic_value(node1, node2)
+    }
+}
+
+///
+/// Finds shortest paths with A*, which is guided by a heuristic estimate of the distance from each
+/// node to the goal. The path is only guaranteed to be the shortest when the heuristic never
+/// overestimates. Nodes are reopened when a cheaper way to them turns up, which only happens when
+/// the heuristic isn't also consistent
+pub struct HeuristicPathFinder<ID : Eq, W, T, G : Graph<ID=ID,Weight=W,Value=T>, H = ValueHeuristic> {
+    graph: G,
+    heuristic: H,
+}
+
+impl<ID : Eq, W, T : Heuristic<ID, T, W>, G : Graph<ID=ID,Weight=W,Value=T>> HeuristicPathFinder<ID, W, T, G> {
+
+    ///
+    /// Creates a path finder whose node values estimate the distances between each other
+    pub fn new(graph: G) -> Self {
+        Self {
+            graph,
+            heuristic: ValueHeuristic,
+        }
+    }
+}
+
+impl<ID : Eq, W, T, G : Graph<ID=ID,Weight=W,Value=T>, H> HeuristicPathFinder<ID, W, T, G, H> {
+
+    ///
+    /// Creates a path finder that estimates distances with `heuristic`, which can be a closure
+    /// taking the node to estimate from and the goal
+    pub fn with_heuristic<WI>(graph: G, heuristic: H) -> Self
+        where H : Heuristic<ID, T, WI> {
+        Self {
+            graph,
+            heuristic,
+        }
+    }
+
+    pub fn get_graph(&self) -> &G {
+        &self.graph
+    }
+}
+
+impl<ID, WI, W, T, G, H> PathFinder<ID, WI> for HeuristicPathFinder<ID, W, T, G, H>
+    where ID : Eq + Clone,
+          WI : Num + PartialOrd + Add + Sub + Clone,
+          W : WeightRepr<WI>,
+          G : Graph<ID=ID, Weight=W, Value=T>,
+          H : Heuristic<ID, T, WI> {
+
+    fn find_path(&self, from: &ID, to: &ID) -> Option<(Vec<ID>, WI)> {
+        let goal = self.graph.get_node(to)?;
+        let estimate = |id: &ID| -> Option<WI> {
+            self.graph.get_node(id).map(|node| self.heuristic.get_heuristic_value(node, goal))
+        };
+
+        let mut closed = Set::new();
+        let mut prev: Map<&ID, &ID> = Map::new();
+        let mut distance = Map::new();
+        distance.insert(from, WI::zero());
+
+        let mut queue = BinaryHeap::new();
+        queue.push(IdWrapper(from, Some(estimate(from)?)));
+
+        while let Some(IdWrapper(current, _)) = queue.pop() {
+            if current == to {
+                let mut path = vec![current.clone()];
+                let mut ptr = current;
+                while let Some(&previous) = prev.get(ptr) {
+                    path.push(previous.clone());
+                    ptr = previous;
+                }
+                path.reverse();
+                return Some((path, distance.remove(current).expect("The goal has a distance")));
+            }
+            if !closed.insert(current) {
+                continue;
+            }
+
+            let current_distance: WI = distance.get(current).cloned().expect("Queued nodes have a distance");
+            for adj in self.graph.get_adjacent(current) {
+                let weight = cheapest_weight(&self.graph, current, adj).expect("Adjacent nodes have an edge");
+                let new_distance = current_distance.clone() + weight;
+                if distance.get(adj).is_none_or(|old| new_distance < *old) {
+                    closed.remove(adj);
+                    let priority = new_distance.clone() + estimate(adj).expect("Adjacent nodes exist");
+                    distance.insert(adj, new_distance);
+                    prev.insert(adj, current);
+                    queue.push(IdWrapper(adj, Some(priority)));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use crate::pathing::spatial::{HeuristicPathFinder, Point};
+    use crate::pathing::PathFinder;
+    use crate::directed::Undirected;
+    use crate::{HashGraph, Graph, Node};
+
+    ///
+    /// A 4 by 4 grid of points one apart, with a wall along x = 2 that can only be crossed at the top
+    fn walled_grid() -> HashGraph<(i32, i32), f64, Point<f64>> {
+        let mut map = HashGraph::new();
+        for x in 0..4 {
+            for y in 0..4 {
+                map.add_node_with((x, y), Point::newi(x, y)).unwrap();
+            }
+        }
+        for x in 0..4 {
+            for y in 0..4 {
+                for (dx, dy) in [(1, 0), (0, 1), (-1, 0), (0, -1)] {
+                    let next = (x + dx, y + dy);
+                    let crosses_wall = (x == 1 && dx == 1 || x == 2 && dx == -1) && y != 3;
+                    if map.contains_node(&next) && !crosses_wall {
+                        map.add_edge_distance(&(x, y), &next).unwrap();
+                    }
+                }
+            }
+        }
+        map
+    }
+
+    #[test]
+    fn point_heuristic() {
+        let finder = HeuristicPathFinder::new(walled_grid());
+        let (path, distance): (Vec<(i32, i32)>, f64) = finder.find_path(&(0, 0), &(3, 0)).unwrap();
+        assert_eq!(distance, 9.0);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(3, 0)));
+        assert!(path.contains(&(1, 3)) && path.contains(&(2, 3)), "The only way through the wall is at the top");
+
+        let same: Option<(Vec<(i32, i32)>, f64)> = finder.find_path(&(2, 2), &(2, 2));
+        assert_eq!(same, Some((vec![(2, 2)], 0.0)));
+    }
+
+    #[test]
+    fn closure_heuristic() {
+        let mut line = Undirected::from(HashGraph::<u32, u32, ()>::new());
+        line.add_nodes(0..5).unwrap();
+        for i in 0..4 {
+            line.add_edge_with(&i, &(i + 1), 2).unwrap();
+        }
+        line.add_edge_with(&0, &4, 10).unwrap();
+
+        let finder = HeuristicPathFinder::with_heuristic(line, |node: &Node<u32, ()>, goal: &Node<u32, ()>| {
+            if goal.get_id() > node.get_id() { goal.get_id() - node.get_id() } else { node.get_id() - goal.get_id() }
+        });
+        assert_eq!(finder.find_path(&0, &4), Some((vec![0, 1, 2, 3, 4], 8)));
+        assert_eq!(finder.find_path(&0, &7), None);
+    }
+
+    #[test]
+    fn inconsistent_heuristic() {
+        let mut g = HashGraph::<char, u32, ()>::new();
+        for id in "sabcg".chars() {
+            g.add_node_with(id, ()).unwrap();
+        }
+        for (u, v, w) in [('s', 'a', 1), ('s', 'b', 1), ('a', 'c', 3), ('b', 'c', 1), ('c', 'g', 10)] {
+            g.add_edge_with(&u, &v, w).unwrap();
+        }
+
+        // Never overestimates, but makes c look closer through a than through b
+        let finder = HeuristicPathFinder::with_heuristic(g, |node: &Node<char, ()>, _: &Node<char, ()>| {
+            if *node.get_id() == 'b' { 11 } else { 0 }
+        });
+        assert_eq!(finder.find_path(&'s', &'g'), Some((vec!['s', 'b', 'c', 'g'], 12)));
+    }
+}
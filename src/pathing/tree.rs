@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+///
+/// The shortest known way to reach every node found from a single source: the distance to each
+/// node and the node before it on a shortest path
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortestPathTree<ID : Eq + Hash, W> {
+    source: ID,
+    reached: HashMap<ID, (W, Option<ID>)>,
+}
+
+impl<ID : Eq + Hash + Clone, W> ShortestPathTree<ID, W> {
+
+    pub(crate) fn new(source: ID, reached: HashMap<ID, (W, Option<ID>)>) -> Self {
+        Self { source, reached }
+    }
+
+    pub fn source(&self) -> &ID {
+        &self.source
+    }
+
+    pub fn contains(&self, id: &ID) -> bool {
+        self.reached.contains_key(id)
+    }
+
+    ///
+    /// The length of the shortest path from the source to `id`, if it was reached
+    pub fn distance(&self, id: &ID) -> Option<&W> {
+        self.reached.get(id).map(|(distance, _)| distance)
+    }
+
+    ///
+    /// The node before `id` on the shortest path to it, which is `None` for the source
+    pub fn predecessor(&self, id: &ID) -> Option<&ID> {
+        self.reached.get(id).and_then(|(_, previous)| previous.as_ref())
+    }
+
+    ///
+    /// Every node that was reached, with its distance from the source
+    pub fn distances(&self) -> impl Iterator<Item=(&ID, &W)> {
+        self.reached.iter().map(|(id, (distance, _))| (id, distance))
+    }
+
+    ///
+    /// The shortest path from the source to `id`, including both ends
+    pub fn path_to(&self, id: &ID) -> Option<Vec<ID>> {
+        let (_, mut previous) = self.reached.get_key_value(id)?;
+        let mut path = vec![id.clone()];
+        while let (_, Some(next)) = previous {
+            path.push(next.clone());
+            previous = &self.reached[next];
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.reached.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reached.is_empty()
+    }
+}
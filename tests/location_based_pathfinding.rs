@@ -1,6 +1,7 @@
 use graph_rs::{HashGraph, GraphTools};
 use graph_rs::directed::Directed;
-use graph_rs::pathing::spatial::Point;
+use graph_rs::pathing::spatial::{Point, HeuristicPathFinder};
+use graph_rs::pathing::PathFinder;
 
 #[test]
 fn small_test() {
@@ -18,4 +19,30 @@ fn small_test() {
     assert_eq!(point_ids, vec![0, 1, 2]);
 
 
-}
\ No newline at end of file
+}
+
+#[test]
+fn shortest_route_between_points() {
+
+    let mut map = HashGraph::<usize, f64, Point<f64>>::new();
+
+    let ids = map.add_nodes_auto_id(
+        vec![
+            Point::newi(0, 0),
+            Point::newi(3, 4),
+            Point::newi(6, 0),
+            Point::newi(3, -1)
+        ]
+    ).unwrap();
+
+    for (u, v) in [(0, 1), (1, 2), (0, 3), (3, 2)] {
+        map.add_edge_distance(&ids[u], &ids[v]).unwrap();
+    }
+
+    let pathfinder = HeuristicPathFinder::new(map);
+    let route: Option<(Vec<usize>, f64)> = pathfinder.find_path(&0, &2);
+    let (path, distance) = route.expect("There is a route between the two points");
+    assert_eq!(path, vec![0, 3, 2]);
+    assert!((distance - 2.0 * 10f64.sqrt()).abs() < 1e-9);
+    assert!(pathfinder.find_path(&2, &0).map(|(_, weight): (Vec<usize>, f64)| weight).is_none(), "Edges only go one way");
+}
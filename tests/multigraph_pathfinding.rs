@@ -0,0 +1,27 @@
+use graph_rs::{HashMultiGraph, Graph};
+use graph_rs::directed::Undirected;
+use graph_rs::pathing::{StatelessPathFinder, PathFinder};
+
+#[test]
+fn cheapest_parallel_edge() {
+    let mut transit = Undirected::from(HashMultiGraph::<usize, usize, ()>::new());
+
+    transit.add_nodes(0..3).unwrap();
+    transit.add_edge_with(&0, &1, 12).unwrap();
+    transit.add_edge_with(&0, &1, 4).unwrap();
+    transit.add_edge_with(&1, &2, 3).unwrap();
+    transit.add_edge_with(&1, &2, 9).unwrap();
+    transit.add_edge_with(&0, &2, 10).unwrap();
+
+    assert_eq!(transit.num_edges(), 5);
+    assert_eq!(transit.edges().count(), 5, "Every parallel edge should be listed");
+
+    let pathfinder = StatelessPathFinder::new(transit);
+    let path: Option<(Vec<_>, usize)> = pathfinder.find_path(&0, &2);
+    if let Some((path, weight)) = path {
+        assert_eq!(path, vec![0, 1, 2]);
+        assert_eq!(weight, 7);
+    } else {
+        panic!("There is a path between the two stops")
+    }
+}
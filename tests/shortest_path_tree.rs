@@ -0,0 +1,55 @@
+use graph_rs::{HashGraph, Graph};
+use graph_rs::directed::Undirected;
+use graph_rs::pathing::{StatelessPathFinder, PathFinder};
+
+fn sites() -> Undirected<usize, u32, (), HashGraph<usize, u32, ()>> {
+    let mut sites = Undirected::from(HashGraph::<usize, u32, ()>::new());
+    sites.add_nodes(0..6).unwrap();
+    for (u, v, w) in [(0, 1, 7), (0, 2, 9), (0, 5, 14), (1, 2, 10), (1, 3, 15), (2, 3, 11), (2, 5, 2), (3, 4, 6)] {
+        sites.add_edge_with(&u, &v, w).unwrap();
+    }
+    sites.add_node(6).unwrap();
+    sites
+}
+
+#[test]
+fn every_reachable_site() {
+    let pathfinder = StatelessPathFinder::new(sites());
+    let tree = pathfinder.shortest_paths::<u32>(&0);
+
+    assert_eq!(tree.source(), &0);
+    assert_eq!(tree.len(), 6);
+    assert!(!tree.contains(&6), "Site 6 has no roads");
+    assert_eq!(tree.distance(&4), Some(&26));
+    assert_eq!(tree.distance(&5), Some(&11));
+    assert_eq!(tree.predecessor(&5), Some(&2));
+    assert_eq!(tree.path_to(&4), Some(vec![0, 2, 3, 4]));
+    assert_eq!(tree.path_to(&0), Some(vec![0]));
+
+    for (id, distance) in tree.distances() {
+        let (_, expected): (Vec<usize>, u32) = pathfinder.find_path(&0, id).unwrap();
+        assert_eq!(*distance, expected);
+    }
+
+    assert!(pathfinder.shortest_paths::<u32>(&10).is_empty());
+}
+
+#[test]
+fn limited_search() {
+    let pathfinder = StatelessPathFinder::new(sites());
+
+    let nearby = pathfinder.shortest_paths_within(&0, 10u32);
+    assert_eq!(nearby.len(), 3);
+    assert!(nearby.contains(&1) && nearby.contains(&2));
+    assert!(!nearby.contains(&5));
+
+    let mut seen = vec![];
+    let until = pathfinder.shortest_paths_until(&0, |id: &usize, _: &u32| {
+        seen.push(*id);
+        *id == 3
+    });
+    assert_eq!(seen, vec![0, 1, 2, 5, 3]);
+    assert_eq!(until.len(), 5);
+    assert_eq!(until.distance(&3), Some(&20));
+    assert!(!until.contains(&4));
+}
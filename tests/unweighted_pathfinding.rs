@@ -1,5 +1,5 @@
-use graph_rs::directed::{Undirected, new_hashed_undirected};
-use graph_rs::HashGraph;
+use graph_rs::directed::{Undirected, Directed, new_hashed_undirected};
+use graph_rs::{HashGraph, BTreeGraph, Graph, GraphLookup};
 use graph_rs::pathing::{StatelessPathFinder, PathFinder};
 
 #[test]
@@ -26,3 +26,48 @@ fn small_path() {
 
 }
 
+
+#[test]
+fn string_ids() {
+    let mut undirected = Undirected::from(HashGraph::<String, ()>::new());
+
+    for stop in &["home", "park", "shop", "work"] {
+        undirected.add_node(stop.to_string()).unwrap();
+    }
+    undirected.add_edge(&"home".to_string(), &"park".to_string()).unwrap();
+    undirected.add_edge(&"park".to_string(), &"work".to_string()).unwrap();
+    undirected.add_edge(&"home".to_string(), &"shop".to_string()).unwrap();
+
+    let pathfinder = StatelessPathFinder::new(undirected);
+
+    let path: Option<(Vec<_>, usize)> = pathfinder.find_path(&"work".to_string(), &"shop".to_string());
+    let (path, weight) = path.expect("There is a path between the two points");
+    assert_eq!(path, vec!["work", "park", "home", "shop"]);
+    assert_eq!(weight, 3);
+}
+
+fn stop_value<'a, G: GraphLookup<str>>(graph: &'a G, stop: &str) -> Option<&'a G::Value> {
+    graph.lookup(stop)
+}
+
+#[test]
+fn borrowed_lookups() {
+    let mut undirected = Undirected::from(HashGraph::<String, u32, u32>::new());
+    undirected.add_node_with("home".to_string(), 1).unwrap();
+    undirected.add_node_with("park".to_string(), 2).unwrap();
+    undirected.add_edge_with(&"home".to_string(), &"park".to_string(), 5).unwrap();
+
+    assert!(undirected.has_node("home"));
+    assert!(undirected.has_edge("park", "home"));
+    assert_eq!(undirected.lookup_weight("home", "park"), Some(&5));
+    *undirected.lookup_mut("park").unwrap() += 1;
+    assert_eq!(stop_value(&undirected, "park"), Some(&3));
+    assert_eq!(stop_value(&undirected, "shop"), None);
+
+    let mut ordered = BTreeGraph::<String, (), u32>::new();
+    ordered.add_node_with("work".to_string(), 4).unwrap();
+    assert_eq!(stop_value(&ordered, "work"), Some(&4));
+
+    let directed = Directed::from(HashGraph::<String, (), u32>::new());
+    assert!(!directed.has_node("work"));
+}